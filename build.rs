@@ -0,0 +1,15 @@
+// Copyright 2026 the authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+use std::env;
+
+fn main() {
+    println!("cargo::rerun-if-changed=build.rs");
+    println!("cargo::rustc-check-cfg=cfg(thumb)");
+    // Stable rustc doesn't expose `target_feature = "thumb-mode"` as a cfg, so detect Thumb targets
+    // by name instead.
+    if env::var("TARGET").is_ok_and(|target| target.starts_with("thumb")) {
+        println!("cargo::rustc-cfg=thumb");
+    }
+}
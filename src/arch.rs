@@ -4,21 +4,23 @@
 
 //! Standard Arm architecture calls.
 
+pub mod backend;
 mod calls;
 pub mod error;
 
+use crate::FunctionId;
 pub use calls::{
     arch_workaround_1, arch_workaround_2, arch_workaround_3, features, soc_id, version,
 };
 use core::fmt::{self, Debug, Display, Formatter};
 pub use error::Error;
 
-pub const SMCCC_VERSION: u32 = 0x8000_0000;
-pub const SMCCC_ARCH_FEATURES: u32 = 0x8000_0001;
-pub const SMCCC_ARCH_SOC_ID: u32 = 0x8000_0002;
-pub const SMCCC_ARCH_WORKAROUND_1: u32 = 0x8000_8000;
-pub const SMCCC_ARCH_WORKAROUND_2: u32 = 0x8000_7FFF;
-pub const SMCCC_ARCH_WORKAROUND_3: u32 = 0x8000_3FFF;
+pub const SMCCC_VERSION: FunctionId = FunctionId::new(0x8000_0000);
+pub const SMCCC_ARCH_FEATURES: FunctionId = FunctionId::new(0x8000_0001);
+pub const SMCCC_ARCH_SOC_ID: FunctionId = FunctionId::new(0x8000_0002);
+pub const SMCCC_ARCH_WORKAROUND_1: FunctionId = FunctionId::new(0x8000_8000);
+pub const SMCCC_ARCH_WORKAROUND_2: FunctionId = FunctionId::new(0x8000_7FFF);
+pub const SMCCC_ARCH_WORKAROUND_3: FunctionId = FunctionId::new(0x8000_3FFF);
 
 /// A version of the SMC Calling Convention.
 #[derive(Copy, Clone, Eq, Ord, PartialEq, PartialOrd)]
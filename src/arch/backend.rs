@@ -0,0 +1,43 @@
+// Copyright 2023 the authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Functions to make Arm Architecture Service calls via a [`Backend`](crate::Backend) instance,
+//! which may carry state.
+//!
+//! These are the same as the functions in [`arch`](super), but take the conduit as a reference
+//! rather than a type parameter.
+
+use super::{
+    SMCCC_ARCH_FEATURES, SMCCC_ARCH_SOC_ID, SMCCC_ARCH_WORKAROUND_1, SMCCC_ARCH_WORKAROUND_2,
+    SMCCC_ARCH_WORKAROUND_3, SMCCC_VERSION, SocIdType, Version, error::Error,
+};
+use crate::{FunctionId, define_calls};
+
+// `SMCCC_VERSION` may be called on SMCCC 1.0 implementations, but the other functions here were
+// added in SMCCC 1.1 so can rely on it preserving the registers which they don't use.
+define_calls! {
+    /// Returns the implemented version of the SMC Calling Convention.
+    pub fn version() -> Result<Version, Error> = call32_short(SMCCC_VERSION) => try_from;
+
+    /// Returns whether the given Arm Architecture Service function is implemented, and any feature
+    /// flags specific to the function.
+    pub fn features(arch_func_id: FunctionId) -> Result<u32, Error> =
+        call32_short_v1_1(SMCCC_ARCH_FEATURES) => positive;
+
+    /// Returns the SiP defined SoC identification details.
+    pub fn soc_id(soc_id_type: SocIdType) -> Result<u32, Error> =
+        call32_short_v1_1(SMCCC_ARCH_SOC_ID) => positive;
+
+    /// Executes a firmware workaround to mitigate CVE-2017-5715.
+    pub fn arch_workaround_1() -> Result<(), Error> =
+        call32_short_v1_1(SMCCC_ARCH_WORKAROUND_1) => success;
+
+    /// Enables or disables the mitigation for CVE-2018-3639.
+    pub fn arch_workaround_2(enable: bool) -> Result<(), Error> =
+        call32_short_v1_1(SMCCC_ARCH_WORKAROUND_2) => success;
+
+    /// Executes a firmware workaround to mitigate CVE-2017-5715 and CVE-2022-23960.
+    pub fn arch_workaround_3() -> Result<(), Error> =
+        call32_short_v1_1(SMCCC_ARCH_WORKAROUND_3) => success;
+}
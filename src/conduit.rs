@@ -0,0 +1,170 @@
+// Copyright 2026 the authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Selection of the HVC or SMC conduit at runtime.
+
+#[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
+use crate::{Backend, Call, FunctionId, Hvc, Smc, arch::error::NOT_SUPPORTED};
+#[cfg(target_arch = "aarch64")]
+use crate::{Backend64, Call64};
+use core::sync::atomic::{AtomicU8, Ordering};
+
+const UNSET: u8 = 0;
+const NONE: u8 = 1;
+const HVC: u8 = 2;
+const SMC: u8 = 3;
+
+static DEFAULT_CONDUIT: AtomicU8 = AtomicU8::new(UNSET);
+
+/// The conduit to use for SMCCC calls, selected at runtime.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Conduit {
+    /// There is no conduit, so all calls return `NOT_SUPPORTED`.
+    None,
+    /// Use a Hypervisor Call (HVC).
+    Hvc,
+    /// Use a Secure Monitor Call (SMC).
+    Smc,
+}
+
+#[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
+impl Backend for Conduit {
+    fn call32(&self, function: FunctionId, args: [u32; 7]) -> [u32; 8] {
+        match *self {
+            Self::None => [NOT_SUPPORTED as u32, 0, 0, 0, 0, 0, 0, 0],
+            Self::Hvc => <Hvc as Call>::call32(function, args),
+            Self::Smc => <Smc as Call>::call32(function, args),
+        }
+    }
+
+    fn call32_short(&self, function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        match *self {
+            Self::None => [NOT_SUPPORTED as u32, 0, 0, 0],
+            Self::Hvc => <Hvc as Call>::call32_short(function, args),
+            Self::Smc => <Smc as Call>::call32_short(function, args),
+        }
+    }
+
+    fn call32_short_v1_1(&self, function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        match *self {
+            Self::None => [NOT_SUPPORTED as u32, 0, 0, 0],
+            Self::Hvc => <Hvc as Call>::call32_short_v1_1(function, args),
+            Self::Smc => <Smc as Call>::call32_short_v1_1(function, args),
+        }
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+impl Backend64 for Conduit {
+    fn call64(&self, function: FunctionId, args: [u64; 17]) -> [u64; 18] {
+        match *self {
+            Self::None => {
+                let mut ret = [0; 18];
+                ret[0] = NOT_SUPPORTED as u64;
+                ret
+            }
+            Self::Hvc => <Hvc as Call64>::call64(function, args),
+            Self::Smc => <Smc as Call64>::call64(function, args),
+        }
+    }
+
+    fn call64_short(&self, function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        match *self {
+            Self::None => [NOT_SUPPORTED as u64, 0, 0, 0],
+            Self::Hvc => <Hvc as Call64>::call64_short(function, args),
+            Self::Smc => <Smc as Call64>::call64_short(function, args),
+        }
+    }
+
+    fn call64_short_v1_1(&self, function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        match *self {
+            Self::None => [NOT_SUPPORTED as u64, 0, 0, 0],
+            Self::Hvc => <Hvc as Call64>::call64_short_v1_1(function, args),
+            Self::Smc => <Smc as Call64>::call64_short_v1_1(function, args),
+        }
+    }
+}
+
+/// Sets the conduit used by [`DefaultConduit`].
+///
+/// This should be called once during early boot, before any calls are made via
+/// [`DefaultConduit`]. If the default conduit has already been set then it is left unchanged and
+/// the existing value is returned as an error.
+pub fn set_default_conduit(conduit: Conduit) -> Result<(), Conduit> {
+    DEFAULT_CONDUIT
+        .compare_exchange(UNSET, encode(conduit), Ordering::AcqRel, Ordering::Acquire)
+        .map(|_| ())
+        .map_err(decode)
+}
+
+/// Returns the conduit used by [`DefaultConduit`], or [`Conduit::None`] if it has not been set.
+pub fn default_conduit() -> Conduit {
+    decode(DEFAULT_CONDUIT.load(Ordering::Acquire))
+}
+
+fn encode(conduit: Conduit) -> u8 {
+    match conduit {
+        Conduit::None => NONE,
+        Conduit::Hvc => HVC,
+        Conduit::Smc => SMC,
+    }
+}
+
+fn decode(value: u8) -> Conduit {
+    match value {
+        HVC => Conduit::Hvc,
+        SMC => Conduit::Smc,
+        _ => Conduit::None,
+    }
+}
+
+/// Use the conduit set by [`set_default_conduit`].
+///
+/// Until the default conduit is set, all calls return `NOT_SUPPORTED`.
+#[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
+pub struct DefaultConduit;
+
+#[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
+impl Call for DefaultConduit {
+    fn call32(function: FunctionId, args: [u32; 7]) -> [u32; 8] {
+        default_conduit().call32(function, args)
+    }
+
+    fn call32_short(function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        default_conduit().call32_short(function, args)
+    }
+
+    fn call32_short_v1_1(function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        default_conduit().call32_short_v1_1(function, args)
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+impl Call64 for DefaultConduit {
+    fn call64(function: FunctionId, args: [u64; 17]) -> [u64; 18] {
+        default_conduit().call64(function, args)
+    }
+
+    fn call64_short(function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        default_conduit().call64_short(function, args)
+    }
+
+    fn call64_short_v1_1(function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        default_conduit().call64_short_v1_1(function, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_default_once() {
+        assert_eq!(default_conduit(), Conduit::None);
+        assert_eq!(set_default_conduit(Conduit::Smc), Ok(()));
+        assert_eq!(default_conduit(), Conduit::Smc);
+        assert_eq!(set_default_conduit(Conduit::Hvc), Err(Conduit::Smc));
+        assert_eq!(default_conduit(), Conduit::Smc);
+    }
+}
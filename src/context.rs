@@ -0,0 +1,235 @@
+// Copyright 2026 the authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Optional context for errors, recording which call failed and with what arguments.
+//!
+//! Errors returned by the `psci` and `arch` functions don't include the call which failed. To add
+//! it, make calls via a [`Contextual`] wrapper and pass their results to
+//! [`Contextual::add_context`]:
+//!
+//! ```
+//! use smccc::{
+//!     Backend64,
+//!     context::{Contextual, WithContext},
+//!     psci,
+//! };
+//!
+//! fn start_secondary<B: Backend64>(
+//!     conduit: &Contextual<B>,
+//!     mpidr: u64,
+//!     entry_point: u64,
+//! ) -> Result<(), WithContext<psci::Error>> {
+//!     conduit.add_context(psci::backend::cpu_on(conduit, mpidr, entry_point, 0))
+//! }
+//!
+//! # #[cfg(target_arch = "aarch64")]
+//! # fn start_via_smc(mpidr: u64, entry_point: u64) -> Result<(), WithContext<psci::Error>> {
+//! start_secondary(&Contextual::new(smccc::Smc), mpidr, entry_point)
+//! # }
+//! ```
+//!
+//! which on failure gives an error like
+//! `Invalid parameters to PSCI call in PSCI_CPU_ON_64(0x1, 0x80000000, 0x0)`.
+
+use crate::{Backend, Backend64, FunctionId, registry::FormattedCall};
+use core::{
+    cell::Cell,
+    fmt::{self, Display, Formatter},
+};
+
+/// The number of argument registers recorded for context.
+const CONTEXT_ARGS: usize = 3;
+
+/// A call which was made, for error context.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct CallContext {
+    /// The function ID of the call.
+    pub function: FunctionId,
+    /// The first few argument registers of the call.
+    pub args: [u64; CONTEXT_ARGS],
+}
+
+impl Display for CallContext {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            FormattedCall {
+                function: self.function,
+                args: &self.args,
+            }
+        )
+    }
+}
+
+/// An error along with the call which returned it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct WithContext<E> {
+    /// The error returned by the call.
+    pub error: E,
+    /// The call which returned the error, if any was made.
+    pub call: Option<CallContext>,
+}
+
+impl<E: Display> Display for WithContext<E> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+        if let Some(call) = &self.call {
+            write!(f, " in {call}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: core::error::Error + 'static> core::error::Error for WithContext<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Wraps another [`Backend`] to remember the most recent call made through it, so that it can be
+/// attached to an error with [`Contextual::add_context`].
+pub struct Contextual<B> {
+    inner: B,
+    last_call: Cell<Option<CallContext>>,
+}
+
+impl<B> Contextual<B> {
+    /// Creates a new wrapper around the given backend.
+    pub const fn new(inner: B) -> Self {
+        Self {
+            inner,
+            last_call: Cell::new(None),
+        }
+    }
+
+    /// Returns the most recent call made through this wrapper.
+    pub fn last_call(&self) -> Option<CallContext> {
+        self.last_call.get()
+    }
+
+    /// Adds the most recent call made through this wrapper to the error, if any.
+    pub fn add_context<T, E>(&self, result: Result<T, E>) -> Result<T, WithContext<E>> {
+        result.map_err(|error| WithContext {
+            error,
+            call: self.last_call(),
+        })
+    }
+
+    /// Returns the wrapped backend.
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+
+    fn record<T: Copy + Into<u64>>(&self, function: FunctionId, args: &[T]) {
+        self.last_call.set(Some(CallContext {
+            function,
+            args: core::array::from_fn(|i| args[i].into()),
+        }));
+    }
+}
+
+impl<B: Backend> Backend for Contextual<B> {
+    fn call32(&self, function: FunctionId, args: [u32; 7]) -> [u32; 8] {
+        self.record(function, &args);
+        self.inner.call32(function, args)
+    }
+
+    fn call32_short(&self, function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        self.record(function, &args);
+        self.inner.call32_short(function, args)
+    }
+
+    fn call32_short_v1_1(&self, function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        self.record(function, &args);
+        self.inner.call32_short_v1_1(function, args)
+    }
+}
+
+impl<B: Backend64> Backend64 for Contextual<B> {
+    fn call64(&self, function: FunctionId, args: [u64; 17]) -> [u64; 18] {
+        self.record(function, &args);
+        self.inner.call64(function, args)
+    }
+
+    fn call64_short(&self, function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        self.record(function, &args);
+        self.inner.call64_short(function, args)
+    }
+
+    fn call64_short_v1_1(&self, function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        self.record(function, &args);
+        self.inner.call64_short_v1_1(function, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::Mock,
+        psci::{self, Error, PSCI_CPU_ON_32, PSCI_CPU_ON_64},
+    };
+    use std::string::ToString;
+
+    #[test]
+    fn context_added_to_error() {
+        let conduit = Contextual::new(Mock);
+        assert_eq!(
+            conduit
+                .add_context(Err::<(), _>(Error::Denied))
+                .unwrap_err()
+                .call,
+            None
+        );
+
+        Mock::expect_call64(PSCI_CPU_ON_64, &[1, 0x8000_0000, 0], &[-2i64 as u64]);
+        let error = conduit
+            .add_context(psci::backend::cpu_on(&conduit, 1, 0x8000_0000, 0))
+            .unwrap_err();
+        assert_eq!(
+            error,
+            WithContext {
+                error: Error::InvalidParameters,
+                call: Some(CallContext {
+                    function: PSCI_CPU_ON_64,
+                    args: [1, 0x8000_0000, 0],
+                }),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Invalid parameters to PSCI call in PSCI_CPU_ON_64(0x1, 0x80000000, 0x0)"
+        );
+
+        Mock::expect_call32(PSCI_CPU_ON_32, &[2, 0x8000_0000, 0], &[0]);
+        assert_eq!(
+            conduit.add_context(psci::backend::cpu_on_32(&conduit, 2, 0x8000_0000, 0)),
+            Ok(())
+        );
+        Mock::finish();
+    }
+
+    #[test]
+    fn display_unknown_function() {
+        let error = WithContext {
+            error: Error::NotSupported,
+            call: Some(CallContext {
+                function: FunctionId::new(0xC200_0001),
+                args: [1, 2, 3],
+            }),
+        };
+        assert_eq!(
+            error.to_string(),
+            "PSCI call not supported in 0xc2000001(0x1, 0x2, 0x3)"
+        );
+
+        // Trailing zero arguments of unknown functions are omitted.
+        let call = CallContext {
+            function: FunctionId::new(0xC200_0001),
+            args: [1, 0, 0],
+        };
+        assert_eq!(call.to_string(), "0xc2000001(0x1)");
+    }
+}
@@ -0,0 +1,238 @@
+// Copyright 2026 the authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Server-side dispatch of SMCCC calls, for hypervisors and firmware which handle HVC or SMC
+//! traps.
+//!
+//! A [`Dispatcher`] takes the register file of the trapped call, decodes the function ID in w0 and
+//! routes the call to the [`Service`] registered for its owning entity. Calls to unknown functions
+//! return `NOT_SUPPORTED`, as required by the SMC Calling Convention.
+
+use crate::{
+    Convention, FunctionId, OwningEntity, arch::error::NOT_SUPPORTED, function_id::FunctionIdError,
+};
+
+/// The number of possible owning entity numbers.
+const OWNING_ENTITY_COUNT: usize = 64;
+
+/// The results of a call handled by a [`Service`], to be written back to the first result
+/// registers.
+///
+/// Result registers beyond those returned are left unchanged, as SMCCC 1.1 and later require.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Results<T, const N: usize> {
+    values: [T; N],
+    count: usize,
+}
+
+/// The results of an SMC32/HVC32 call, in w0 to w7.
+pub type Results32 = Results<u32, 8>;
+
+/// The results of an SMC64/HVC64 call, in x0 to x17.
+pub type Results64 = Results<u64, 18>;
+
+impl<T: Copy + Default, const N: usize> Results<T, N> {
+    /// Returns the given values in w0 or x0 onwards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more values than result registers.
+    pub fn new(values: &[T]) -> Self {
+        assert!(values.len() <= N, "Too many result values");
+        Self {
+            values: core::array::from_fn(|i| values.get(i).copied().unwrap_or_default()),
+            count: values.len(),
+        }
+    }
+
+    /// Returns the values to be written back to the result registers.
+    pub fn values(&self) -> &[T] {
+        &self.values[..self.count]
+    }
+}
+
+/// A service which handles fast calls in the range of an owning entity.
+///
+/// The function IDs passed to the service never have the SVE live state hint set.
+pub trait Service {
+    /// Handles a call using the 32-bit calling convention.
+    ///
+    /// Returns `None` if the function is not implemented, in which case the caller gets
+    /// `NOT_SUPPORTED`.
+    fn handle32(&mut self, function: FunctionId, args: [u32; 7]) -> Option<Results32> {
+        let _ = (function, args);
+        None
+    }
+
+    /// Handles a call using the 64-bit calling convention.
+    ///
+    /// Returns `None` if the function is not implemented, in which case the caller gets
+    /// `NOT_SUPPORTED`.
+    fn handle64(&mut self, function: FunctionId, args: [u64; 17]) -> Option<Results64> {
+        let _ = (function, args);
+        None
+    }
+}
+
+/// Routes trapped SMCCC calls to the services registered for each owning entity.
+pub struct Dispatcher<'a> {
+    services: [Option<&'a mut dyn Service>; OWNING_ENTITY_COUNT],
+}
+
+impl<'a> Dispatcher<'a> {
+    /// Creates a new dispatcher with no services registered.
+    pub const fn new() -> Self {
+        Self {
+            services: [const { None }; OWNING_ENTITY_COUNT],
+        }
+    }
+
+    /// Registers the given service to handle fast calls for the given owning entity, replacing any
+    /// service previously registered for it.
+    ///
+    /// Returns an error if the index of a Trusted Application or Trusted OS is out of range.
+    pub fn register(
+        &mut self,
+        owning_entity: OwningEntity,
+        service: &'a mut dyn Service,
+    ) -> Result<(), FunctionIdError> {
+        self.services[usize::from(owning_entity.number()?)] = Some(service);
+        Ok(())
+    }
+
+    /// Handles the call described by the given trapped register file, x0 to x17, and writes the
+    /// results back to it.
+    ///
+    /// The SVE live state hint is cleared from the function ID before it is routed. Only the result
+    /// registers which the service returns are written back, and for SMC32/HVC32 calls the upper
+    /// 32 bits of the argument registers are ignored and the results are zero-extended. If the
+    /// function ID is malformed, or no service handles the function, then x0 is set to
+    /// `NOT_SUPPORTED` and the other registers are left unchanged.
+    pub fn dispatch(&mut self, regs: &mut [u64; 18]) {
+        if self.try_dispatch(regs).is_none() {
+            regs[0] = NOT_SUPPORTED as u64;
+        }
+    }
+
+    fn try_dispatch(&mut self, regs: &mut [u64; 18]) -> Option<()> {
+        // The function ID is always passed in w0, regardless of the calling convention.
+        let function = FunctionId::try_from(regs[0] as u32)
+            .ok()?
+            .without_sve_hint();
+        let entity_number = function.owning_entity()?.number().ok()?;
+        let service = self.services[usize::from(entity_number)].as_deref_mut()?;
+        match function.convention() {
+            Convention::Smc32 => {
+                let args = core::array::from_fn(|i| regs[i + 1] as u32);
+                let ret = service.handle32(function, args)?;
+                for (reg, &value) in regs.iter_mut().zip(ret.values()) {
+                    *reg = value.into();
+                }
+            }
+            Convention::Smc64 => {
+                let args = core::array::from_fn(|i| regs[i + 1]);
+                let ret = service.handle64(function, args)?;
+                regs[..ret.values().len()].copy_from_slice(ret.values());
+            }
+        }
+        Some(())
+    }
+}
+
+impl Default for Dispatcher<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::psci::{PSCI_CPU_ON_32, PSCI_CPU_ON_64, PSCI_VERSION};
+
+    struct FakePsci;
+
+    impl Service for FakePsci {
+        fn handle32(&mut self, function: FunctionId, args: [u32; 7]) -> Option<Results32> {
+            match function {
+                PSCI_VERSION => Some(Results::new(&[0x0001_0001])),
+                PSCI_CPU_ON_32 => Some(Results::new(&[args[0], 0])),
+                _ => None,
+            }
+        }
+
+        fn handle64(&mut self, function: FunctionId, args: [u64; 17]) -> Option<Results64> {
+            match function {
+                PSCI_CPU_ON_64 => Some(Results::new(&[args[0]])),
+                _ => None,
+            }
+        }
+    }
+
+    fn regs(function: u64, args: &[u64]) -> [u64; 18] {
+        let mut regs = [0xdead; 18];
+        regs[0] = function;
+        regs[1..=args.len()].copy_from_slice(args);
+        regs
+    }
+
+    #[test]
+    fn dispatch_to_service() {
+        let mut psci = FakePsci;
+        let mut dispatcher = Dispatcher::new();
+        dispatcher
+            .register(OwningEntity::StandardSecure, &mut psci)
+            .unwrap();
+
+        let mut r = regs(PSCI_VERSION.raw().into(), &[]);
+        dispatcher.dispatch(&mut r);
+        assert_eq!(r[0], 0x0001_0001);
+        assert_eq!(r[1], 0xdead);
+
+        // The SVE live state hint is ignored for routing.
+        let mut r = regs(PSCI_VERSION.with_sve_hint().raw().into(), &[]);
+        dispatcher.dispatch(&mut r);
+        assert_eq!(r[0], 0x0001_0001);
+
+        // Upper bits of SMC32 arguments and of the function ID are ignored.
+        let mut r = regs(
+            0x1234_5678_0000_0000 | u64::from(PSCI_CPU_ON_32.raw()),
+            &[0xffff_ffff_0000_0042],
+        );
+        dispatcher.dispatch(&mut r);
+        assert_eq!(r[0], 0x42);
+        assert_eq!(r[1], 0);
+        assert_eq!(r[2], 0xdead);
+
+        let mut r = regs(PSCI_CPU_ON_64.raw().into(), &[0xffff_ffff_0000_0042]);
+        dispatcher.dispatch(&mut r);
+        assert_eq!(r[0], 0xffff_ffff_0000_0042);
+        assert_eq!(r[1], 0xffff_ffff_0000_0042);
+    }
+
+    #[test]
+    fn unknown_calls_not_supported() {
+        let mut psci = FakePsci;
+        let mut dispatcher = Dispatcher::new();
+        dispatcher
+            .register(OwningEntity::StandardSecure, &mut psci)
+            .unwrap();
+
+        for function in [
+            // Unhandled function number.
+            0x8400_0002,
+            // No service registered for the owning entity.
+            0x8200_0000,
+            // Reserved bits set.
+            0x8420_0000,
+            // Yielding call.
+            0x0200_0000,
+        ] {
+            let mut r = regs(function, &[1, 2]);
+            dispatcher.dispatch(&mut r);
+            assert_eq!(r[0] as i64, NOT_SUPPORTED.into());
+            assert_eq!(r[1], 1);
+        }
+    }
+}
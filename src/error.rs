@@ -2,7 +2,7 @@
 // This project is dual-licensed under Apache 2.0 and MIT terms.
 // See LICENSE-APACHE and LICENSE-MIT for details.
 
-//! Utility functions for error handling.
+//! Utility functions for error handling, and an error type covering all services.
 //!
 //! These functions can be combined with the appropriate HVC or SMC functions to wrap calls which
 //! return a single value where negative values indicate an error.
@@ -20,13 +20,45 @@
 //!
 //! # #[cfg(target_arch = "aarch64")]
 //! pub fn system_off() -> Result<(), Error> {
-//!     success_or_error_32(smc32(PSCI_SYSTEM_OFF, [0; 7])[0])
+//!     success_or_error_32(smc32(PSCI_SYSTEM_OFF.into(), [0; 7])[0])
 //! }
 //! ```
 
+use crate::{arch, psci, service};
+
 /// A value commonly returned to indicate a successful SMCCC call.
 pub const SUCCESS: i32 = 0;
 
+/// An error from any service, keeping track of which service returned it.
+///
+/// The same error code may mean different things for different services, e.g. -2 is
+/// `NOT_REQUIRED` for the Arm Architecture Service but `INVALID_PARAMETERS` for PSCI. This type
+/// allows code which calls several services to propagate their errors with `?` without losing that
+/// distinction.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum Error {
+    /// An error from an Arm Architecture Service call.
+    #[error(transparent)]
+    Arch(#[from] arch::Error),
+    /// An error from a PSCI call.
+    #[error(transparent)]
+    Psci(#[from] psci::Error),
+    /// An error from a general service query, such as [`service::uid`].
+    #[error(transparent)]
+    Service(#[from] service::Error),
+}
+
+impl From<Error> for i64 {
+    /// Returns the raw error code which the service returned.
+    fn from(error: Error) -> i64 {
+        match error {
+            Error::Arch(error) => i32::from(error).into(),
+            Error::Psci(error) => error.into(),
+            Error::Service(error) => i32::from(error).into(),
+        }
+    }
+}
+
 /// Converts the given value (returned from an HVC32 or SMC32 call) either to `Ok(())` if it is
 /// equal to [`SUCCESS`], or else an error of the given type.
 pub fn success_or_error_32<E: From<i32>>(value: u32) -> Result<(), E> {
@@ -76,3 +108,37 @@ pub fn positive_or_error_64<E: From<i64>>(value: u64) -> Result<u64, E> {
         Ok(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{arch::SMCCC_VERSION, mock::Mock, psci::PSCI_VERSION};
+
+    fn versions() -> Result<(arch::Version, psci::Version), Error> {
+        Ok((arch::version::<Mock>()?, psci::version::<Mock>()?))
+    }
+
+    #[test]
+    fn propagate_from_either_service() {
+        Mock::expect_call32(SMCCC_VERSION, &[], &[-2i32 as u32]);
+        assert_eq!(versions(), Err(Error::Arch(arch::Error::NotRequired)));
+        Mock::expect_call32(SMCCC_VERSION, &[], &[0x0001_0001]);
+        Mock::expect_call32(PSCI_VERSION, &[], &[-2i32 as u32]);
+        assert_eq!(versions(), Err(Error::Psci(psci::Error::InvalidParameters)));
+        Mock::finish();
+    }
+
+    #[test]
+    fn raw_round_trip() {
+        for raw in [-1, -2, -3, -42] {
+            let arch_error = Error::from(arch::Error::from(raw as i32));
+            assert_eq!(i64::from(arch_error), raw);
+            let psci_error = Error::from(psci::Error::from(raw));
+            assert_eq!(i64::from(psci_error), raw);
+            let service_error = Error::from(service::Error::from(raw as i32));
+            assert_eq!(i64::from(service_error), raw);
+        }
+        let psci_error = Error::from(psci::Error::from(-1i64 << 40));
+        assert_eq!(i64::from(psci_error), -1i64 << 40);
+    }
+}
@@ -0,0 +1,333 @@
+// Copyright 2026 the authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Types for building and decoding SMCCC function identifiers.
+
+use core::fmt::{self, Debug, Display, Formatter};
+
+const FAST_CALL: u32 = 1 << 31;
+const SMC64: u32 = 1 << 30;
+const OWNING_ENTITY_SHIFT: u32 = 24;
+const OWNING_ENTITY_MASK: u32 = 0x3f << OWNING_ENTITY_SHIFT;
+const FAST_CALL_MBZ_MASK: u32 = 0x00fe_0000;
+const SVE_HINT: u32 = 1 << 16;
+const FUNCTION_NUMBER_MASK: u32 = 0xffff;
+
+const TRUSTED_APPLICATION_BASE: u8 = 48;
+const TRUSTED_APPLICATION_COUNT: u8 = 2;
+const TRUSTED_OS_BASE: u8 = 50;
+const TRUSTED_OS_COUNT: u8 = 14;
+
+/// Errors for malformed SMCCC function identifiers.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum FunctionIdError {
+    /// A fast call function ID had some of its must-be-zero bits set.
+    #[error("Function ID {0:#010x} has reserved bits set")]
+    ReservedBitsSet(u32),
+    /// A fast call function ID used an owning entity number which is reserved.
+    #[error("Owning entity number {0} is reserved")]
+    ReservedOwningEntity(u8),
+    /// An owning entity had an index outside of its range.
+    #[error("Owning entity {0:?} is out of range")]
+    InvalidOwningEntity(OwningEntity),
+}
+
+/// Whether a call uses the SMC32/HVC32 or SMC64/HVC64 calling convention.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Convention {
+    /// The 32-bit calling convention.
+    Smc32,
+    /// The 64-bit calling convention.
+    Smc64,
+}
+
+/// The service which owns a range of fast call function IDs.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OwningEntity {
+    /// Arm Architecture calls.
+    ArmArchitecture,
+    /// CPU service calls.
+    Cpu,
+    /// SiP service calls.
+    Sip,
+    /// OEM service calls.
+    Oem,
+    /// Standard Secure service calls, such as PSCI.
+    StandardSecure,
+    /// Standard Hypervisor service calls.
+    StandardHypervisor,
+    /// Vendor specific Hypervisor service calls.
+    VendorHypervisor,
+    /// Vendor specific EL3 monitor calls.
+    VendorEl3Monitor,
+    /// Trusted Application calls, with an index from 0 to 1 within the range.
+    TrustedApplication(u8),
+    /// Trusted OS calls, with an index from 0 to 13 within the range.
+    TrustedOs(u8),
+}
+
+impl OwningEntity {
+    /// Returns the owning entity number used in bits 29:24 of the function ID.
+    ///
+    /// Returns an error if the index of a Trusted Application or Trusted OS is out of range.
+    pub const fn number(self) -> Result<u8, FunctionIdError> {
+        match self {
+            Self::ArmArchitecture => Ok(0),
+            Self::Cpu => Ok(1),
+            Self::Sip => Ok(2),
+            Self::Oem => Ok(3),
+            Self::StandardSecure => Ok(4),
+            Self::StandardHypervisor => Ok(5),
+            Self::VendorHypervisor => Ok(6),
+            Self::VendorEl3Monitor => Ok(7),
+            Self::TrustedApplication(index) if index < TRUSTED_APPLICATION_COUNT => {
+                Ok(TRUSTED_APPLICATION_BASE + index)
+            }
+            Self::TrustedOs(index) if index < TRUSTED_OS_COUNT => Ok(TRUSTED_OS_BASE + index),
+            _ => Err(FunctionIdError::InvalidOwningEntity(self)),
+        }
+    }
+
+    /// Returns the owning entity with the given number, or an error if it is reserved.
+    pub const fn from_number(number: u8) -> Result<Self, FunctionIdError> {
+        match number {
+            0 => Ok(Self::ArmArchitecture),
+            1 => Ok(Self::Cpu),
+            2 => Ok(Self::Sip),
+            3 => Ok(Self::Oem),
+            4 => Ok(Self::StandardSecure),
+            5 => Ok(Self::StandardHypervisor),
+            6 => Ok(Self::VendorHypervisor),
+            7 => Ok(Self::VendorEl3Monitor),
+            48..=49 => Ok(Self::TrustedApplication(number - TRUSTED_APPLICATION_BASE)),
+            50..=63 => Ok(Self::TrustedOs(number - TRUSTED_OS_BASE)),
+            _ => Err(FunctionIdError::ReservedOwningEntity(number)),
+        }
+    }
+}
+
+impl TryFrom<u8> for OwningEntity {
+    type Error = FunctionIdError;
+
+    fn try_from(number: u8) -> Result<Self, FunctionIdError> {
+        Self::from_number(number)
+    }
+}
+
+/// An SMCCC function identifier, as passed in w0.
+///
+/// Fast call IDs are checked to have no reserved bits set and a non-reserved owning entity.
+/// Yielding call IDs are not otherwise interpreted.
+#[derive(Copy, Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct FunctionId(u32);
+
+impl FunctionId {
+    /// Returns the function ID with the given raw value.
+    ///
+    /// This is intended for defining constants, where the check happens at compile time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is not a well-formed function ID.
+    pub const fn new(raw: u32) -> Self {
+        match Self::from_raw(raw) {
+            Ok(function_id) => function_id,
+            Err(_) => panic!("Malformed SMCCC function ID"),
+        }
+    }
+
+    /// Builds the ID of a fast call.
+    pub const fn fast(
+        convention: Convention,
+        owning_entity: OwningEntity,
+        function_number: u16,
+    ) -> Result<Self, FunctionIdError> {
+        let entity_number = match owning_entity.number() {
+            Ok(number) => number,
+            Err(e) => return Err(e),
+        };
+        let convention_bit = match convention {
+            Convention::Smc32 => 0,
+            Convention::Smc64 => SMC64,
+        };
+        Ok(Self(
+            FAST_CALL
+                | convention_bit
+                | (entity_number as u32) << OWNING_ENTITY_SHIFT
+                | function_number as u32,
+        ))
+    }
+
+    /// Returns the function ID with the given raw value, or an error if it is malformed.
+    pub const fn from_raw(raw: u32) -> Result<Self, FunctionIdError> {
+        if raw & FAST_CALL != 0 {
+            if raw & FAST_CALL_MBZ_MASK != 0 {
+                return Err(FunctionIdError::ReservedBitsSet(raw));
+            }
+            if let Err(e) =
+                OwningEntity::from_number(((raw & OWNING_ENTITY_MASK) >> OWNING_ENTITY_SHIFT) as u8)
+            {
+                return Err(e);
+            }
+        }
+        Ok(Self(raw))
+    }
+
+    /// Returns the raw value of the function ID.
+    pub const fn raw(self) -> u32 {
+        self.0
+    }
+
+    /// Returns whether this is a fast call, rather than a yielding call.
+    pub const fn is_fast(self) -> bool {
+        self.0 & FAST_CALL != 0
+    }
+
+    /// Returns which calling convention the function uses.
+    pub const fn convention(self) -> Convention {
+        if self.0 & SMC64 != 0 {
+            Convention::Smc64
+        } else {
+            Convention::Smc32
+        }
+    }
+
+    /// Returns the owning entity of a fast call, or `None` for a yielding call.
+    pub const fn owning_entity(self) -> Option<OwningEntity> {
+        if !self.is_fast() {
+            return None;
+        }
+        match OwningEntity::from_number(
+            ((self.0 & OWNING_ENTITY_MASK) >> OWNING_ENTITY_SHIFT) as u8,
+        ) {
+            Ok(owning_entity) => Some(owning_entity),
+            Err(_) => None,
+        }
+    }
+
+    /// Returns a copy of this function ID with the SMCCC 1.3 hint set to indicate that the caller
+    /// has no live SVE state.
+    ///
+    /// The hint is only defined for fast calls, so yielding call IDs are returned unchanged. Firmware
+    /// implementing an SMCCC version before 1.3 will treat a hinted ID as unknown, so see
+    /// [`NoSveLiveState`](crate::NoSveLiveState) for a way to set it safely.
+    pub const fn with_sve_hint(self) -> Self {
+        if self.is_fast() {
+            Self(self.0 | SVE_HINT)
+        } else {
+            self
+        }
+    }
+
+    /// Returns a copy of this function ID with the SVE live state hint cleared.
+    pub const fn without_sve_hint(self) -> Self {
+        if self.is_fast() {
+            Self(self.0 & !SVE_HINT)
+        } else {
+            self
+        }
+    }
+
+    /// Returns whether this is a fast call with the hint set that the caller has no live SVE state.
+    pub const fn has_sve_hint(self) -> bool {
+        self.is_fast() && self.0 & SVE_HINT != 0
+    }
+
+    /// Returns the function number of a fast call within the range of its owning entity.
+    pub const fn function_number(self) -> u16 {
+        (self.0 & FUNCTION_NUMBER_MASK) as u16
+    }
+}
+
+impl Debug for FunctionId {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "FunctionId({:#010x})", self.0)
+    }
+}
+
+impl Display for FunctionId {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:#010x}", self.0)
+    }
+}
+
+impl From<FunctionId> for u32 {
+    fn from(function_id: FunctionId) -> Self {
+        function_id.0
+    }
+}
+
+impl TryFrom<u32> for FunctionId {
+    type Error = FunctionIdError;
+
+    fn try_from(raw: u32) -> Result<Self, FunctionIdError> {
+        Self::from_raw(raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_and_decode() {
+        let function_id =
+            FunctionId::fast(Convention::Smc64, OwningEntity::StandardSecure, 3).unwrap();
+        assert_eq!(function_id.raw(), 0xC400_0003);
+        assert!(function_id.is_fast());
+        assert_eq!(function_id.convention(), Convention::Smc64);
+        assert_eq!(
+            function_id.owning_entity(),
+            Some(OwningEntity::StandardSecure)
+        );
+        assert_eq!(function_id.function_number(), 3);
+
+        let function_id =
+            FunctionId::fast(Convention::Smc32, OwningEntity::TrustedOs(13), 0xff01).unwrap();
+        assert_eq!(function_id.raw(), 0xBF00_FF01);
+        assert_eq!(
+            FunctionId::try_from(0xBF00_FF01).unwrap().owning_entity(),
+            Some(OwningEntity::TrustedOs(13))
+        );
+    }
+
+    #[test]
+    fn reject_malformed() {
+        assert_eq!(
+            FunctionId::try_from(0x8402_0000),
+            Err(FunctionIdError::ReservedBitsSet(0x8402_0000))
+        );
+        assert_eq!(
+            FunctionId::try_from(0x8800_0000),
+            Err(FunctionIdError::ReservedOwningEntity(8))
+        );
+        assert_eq!(
+            FunctionId::fast(Convention::Smc32, OwningEntity::TrustedApplication(2), 0),
+            Err(FunctionIdError::InvalidOwningEntity(
+                OwningEntity::TrustedApplication(2)
+            ))
+        );
+    }
+
+    #[test]
+    fn sve_hint() {
+        let function_id = FunctionId::new(0xC400_0003).with_sve_hint();
+        assert_eq!(function_id.raw(), 0xC401_0003);
+        assert!(function_id.has_sve_hint());
+        assert_eq!(function_id.function_number(), 3);
+        assert_eq!(FunctionId::try_from(0xC401_0003), Ok(function_id));
+        assert_eq!(function_id.without_sve_hint().raw(), 0xC400_0003);
+        assert_eq!(
+            FunctionId::new(0x0200_0001).with_sve_hint().raw(),
+            0x0200_0001
+        );
+    }
+
+    #[test]
+    fn yielding() {
+        let function_id = FunctionId::try_from(0x0200_0001).unwrap();
+        assert!(!function_id.is_fast());
+        assert_eq!(function_id.owning_entity(), None);
+    }
+}
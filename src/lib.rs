@@ -2,22 +2,55 @@
 // This project is dual-licensed under Apache 2.0 and MIT terms.
 // See LICENSE-APACHE and LICENSE-MIT for details.
 
-//! Functions for version 1.4 of the Arm SMC Calling Convention and version 1.1 of the Arm Power
-//! State Coordination Interface (PSCI) version 1.1, and relevant constants.
+//! Functions for version 1.4 of the Arm SMC Calling Convention and version 1.3 of the Arm Power
+//! State Coordination Interface (PSCI), and relevant constants.
 //!
 //! Note that the PSCI and SMCCC arch calls may be made via either HVC or SMC. You can choose which
-//! one to use by passing either [`Hvc`] or [`Smc`] as a type parameter to the relevant function.
+//! one to use by passing either `Hvc` or `Smc` as a type parameter to the relevant function,
+//! e.g. `psci::cpu_off::<Smc>()`. If the conduit is only known at runtime (e.g. from the
+//! devicetree), you can set it once with [`set_default_conduit`] and then use `DefaultConduit`.
 //!
-//! This crate currently only supports aarch64 and the SMC64 versions of the PSCI calls, in the
-//! cases that both SMC32 and SMC64 versions exist.
+//! To use a conduit instance instead, such as a [`Conduit`] or any other type implementing
+//! [`Backend`] which may carry state, pass a reference to it as the first argument of the
+//! functions in [`psci::backend`] or [`arch::backend`], e.g. `psci::backend::cpu_off(&conduit)`.
+//!
+//! Functions using the SMC64 calling convention require a conduit implementing [`Backend64`], which
+//! `Hvc` and `Smc` only do on aarch64. On 32-bit Arm, use the `_32` variants of the PSCI calls
+//! instead.
 
 #![no_std]
 #![deny(unsafe_op_in_unsafe_fn)]
 #![deny(clippy::undocumented_unsafe_blocks)]
 
+#[cfg(any(test, feature = "mock"))]
+extern crate std;
+
 pub mod arch;
+pub mod conduit;
+pub mod context;
+pub mod dispatch;
 pub mod error;
+pub mod function_id;
+#[cfg(feature = "log")]
+pub mod logging;
+#[doc(hidden)]
+pub mod macros;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+pub mod profiling;
 pub mod psci;
+pub mod registry;
+pub mod service;
+pub mod trace;
+pub mod uuid;
+
+#[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
+pub use conduit::DefaultConduit;
+pub use conduit::{Conduit, default_conduit, set_default_conduit};
+use core::sync::atomic::{AtomicU8, Ordering};
+pub use error::Error;
+pub use function_id::{Convention, FunctionId, OwningEntity};
+pub use uuid::Uuid;
 
 /// Use a Hypervisor Call (HVC).
 #[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
@@ -27,42 +60,349 @@ pub struct Hvc;
 #[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
 pub struct Smc;
 
-/// Functions to make an HVC or SMC call.
+/// Functions to make an HVC or SMC call using the 32-bit calling convention.
 pub trait Call {
     /// Makes a call using the 32-bit calling convention.
-    fn call32(function: u32, args: [u32; 7]) -> [u32; 8];
+    fn call32(function: FunctionId, args: [u32; 7]) -> [u32; 8];
+
+    /// Makes a call using the 32-bit calling convention, for a function with at most three
+    /// arguments and four results.
+    ///
+    /// Only the SMCCC 1.0 register preservation rules are assumed, so implementations must treat
+    /// x4 to x17 (or r4 to r7 on 32-bit Arm) as clobbered, but needn't bind them.
+    fn call32_short(function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        let ret = Self::call32(function, [args[0], args[1], args[2], 0, 0, 0, 0]);
+        [ret[0], ret[1], ret[2], ret[3]]
+    }
+
+    /// Like [`Call::call32_short`], but relies on the callee preserving all registers other than
+    /// those used for results, as required by SMCCC 1.1 and later.
+    ///
+    /// This must only be used for functions which were added in SMCCC 1.1 or later, or after
+    /// checking the SMCCC version.
+    fn call32_short_v1_1(function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        Self::call32_short(function, args)
+    }
+}
+
+/// Functions to make an HVC or SMC call using the 64-bit calling convention.
+///
+/// This is only implemented by `Hvc` and `Smc` on aarch64, so calling a function which needs
+/// the 64-bit calling convention on 32-bit Arm fails to compile rather than at runtime.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't make calls using the 64-bit calling convention",
+    note = "the 64-bit calling convention is only available on aarch64; use the `_32` variant instead"
+)]
+pub trait Call64: Call {
+    /// Makes a call using the 64-bit calling convention.
+    fn call64(function: FunctionId, args: [u64; 17]) -> [u64; 18];
+
+    /// Makes a call using the 64-bit calling convention, for a function with at most three
+    /// arguments and four results.
+    ///
+    /// Only the SMCCC 1.0 register preservation rules are assumed, so implementations must treat
+    /// x4 to x17 as clobbered, but needn't bind them.
+    fn call64_short(function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        let mut full_args = [0; 17];
+        full_args[..3].copy_from_slice(&args);
+        let ret = Self::call64(function, full_args);
+        [ret[0], ret[1], ret[2], ret[3]]
+    }
+
+    /// Like [`Call64::call64_short`], but relies on the callee preserving all registers other than
+    /// those used for results, as required by SMCCC 1.1 and later.
+    ///
+    /// This must only be used for functions which were added in SMCCC 1.1 or later, or after
+    /// checking the SMCCC version.
+    fn call64_short_v1_1(function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        Self::call64_short(function, args)
+    }
+}
+
+/// An instance which can make SMCCC calls using the 32-bit calling convention, possibly carrying
+/// some state.
+///
+/// This is implemented for every type which implements [`Call`], so the functions in
+/// [`psci::backend`] and [`arch::backend`] can be used with either. See [`Call`] for a description of each method.
+pub trait Backend {
+    /// Makes a call using the 32-bit calling convention.
+    fn call32(&self, function: FunctionId, args: [u32; 7]) -> [u32; 8];
+
+    /// Makes a call using the 32-bit calling convention, for a function with at most three
+    /// arguments and four results, assuming the SMCCC 1.0 register preservation rules.
+    fn call32_short(&self, function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        let ret = self.call32(function, [args[0], args[1], args[2], 0, 0, 0, 0]);
+        [ret[0], ret[1], ret[2], ret[3]]
+    }
+
+    /// Like [`Backend::call32_short`], but relies on the SMCCC 1.1 register preservation rules.
+    fn call32_short_v1_1(&self, function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        self.call32_short(function, args)
+    }
+}
+
+/// An instance which can also make SMCCC calls using the 64-bit calling convention.
+///
+/// This is implemented for every type which implements [`Call64`]. See [`Call64`] for a
+/// description of each method.
+///
+/// Functions which use the 64-bit calling convention can't be called with a backend which only
+/// implements [`Backend`]:
+///
+/// ```compile_fail
+/// use smccc::{Backend, FunctionId, psci};
+///
+/// struct Only32;
+///
+/// impl Backend for Only32 {
+///     fn call32(&self, _function: FunctionId, _args: [u32; 7]) -> [u32; 8] {
+///         [0; 8]
+///     }
+/// }
+///
+/// psci::backend::cpu_on(&Only32, 1, 0x8000_0000, 0);
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't make calls using the 64-bit calling convention",
+    note = "the 64-bit calling convention is only available on aarch64; use the `_32` variant instead"
+)]
+pub trait Backend64: Backend {
     /// Makes a call using the 64-bit calling convention.
-    fn call64(function: u32, args: [u64; 17]) -> [u64; 18];
+    fn call64(&self, function: FunctionId, args: [u64; 17]) -> [u64; 18];
+
+    /// Makes a call using the 64-bit calling convention, for a function with at most three
+    /// arguments and four results, assuming the SMCCC 1.0 register preservation rules.
+    fn call64_short(&self, function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        let mut full_args = [0; 17];
+        full_args[..3].copy_from_slice(&args);
+        let ret = self.call64(function, full_args);
+        [ret[0], ret[1], ret[2], ret[3]]
+    }
+
+    /// Like [`Backend64::call64_short`], but relies on the SMCCC 1.1 register preservation rules.
+    fn call64_short_v1_1(&self, function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        self.call64_short(function, args)
+    }
+}
+
+impl<C: Call> Backend for C {
+    fn call32(&self, function: FunctionId, args: [u32; 7]) -> [u32; 8] {
+        C::call32(function, args)
+    }
+
+    fn call32_short(&self, function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        C::call32_short(function, args)
+    }
+
+    fn call32_short_v1_1(&self, function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        C::call32_short_v1_1(function, args)
+    }
+}
+
+impl<C: Call64> Backend64 for C {
+    fn call64(&self, function: FunctionId, args: [u64; 17]) -> [u64; 18] {
+        C::call64(function, args)
+    }
+
+    fn call64_short(&self, function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        C::call64_short(function, args)
+    }
+
+    fn call64_short_v1_1(&self, function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        C::call64_short_v1_1(function, args)
+    }
 }
 
 #[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
 impl Call for Hvc {
-    fn call32(function: u32, args: [u32; 7]) -> [u32; 8] {
-        hvc32(function, args)
+    fn call32(function: FunctionId, args: [u32; 7]) -> [u32; 8] {
+        hvc32(function.into(), args)
     }
 
-    fn call64(function: u32, args: [u64; 17]) -> [u64; 18] {
-        #[cfg(not(target_arch = "aarch64"))]
-        panic!("HVC64 not supported on 32-bit architecture");
-        #[cfg(target_arch = "aarch64")]
-        hvc64(function, args)
+    fn call32_short(function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        hvc32_short(function.into(), args)
+    }
+
+    fn call32_short_v1_1(function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        hvc32_short_v1_1(function.into(), args)
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+impl Call64 for Hvc {
+    fn call64(function: FunctionId, args: [u64; 17]) -> [u64; 18] {
+        hvc64(function.into(), args)
+    }
+
+    fn call64_short(function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        hvc64_short(function.into(), args)
+    }
+
+    fn call64_short_v1_1(function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        hvc64_short_v1_1(function.into(), args)
     }
 }
 
 #[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
 impl Call for Smc {
-    fn call32(function: u32, args: [u32; 7]) -> [u32; 8] {
-        smc32(function, args)
+    fn call32(function: FunctionId, args: [u32; 7]) -> [u32; 8] {
+        smc32(function.into(), args)
     }
 
-    fn call64(function: u32, args: [u64; 17]) -> [u64; 18] {
-        #[cfg(not(target_arch = "aarch64"))]
-        panic!("SMC64 not supported on 32-bit architecture");
-        #[cfg(target_arch = "aarch64")]
-        smc64(function, args)
+    fn call32_short(function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        smc32_short(function.into(), args)
+    }
+
+    fn call32_short_v1_1(function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        smc32_short_v1_1(function.into(), args)
     }
 }
 
+#[cfg(target_arch = "aarch64")]
+impl Call64 for Smc {
+    fn call64(function: FunctionId, args: [u64; 17]) -> [u64; 18] {
+        smc64(function.into(), args)
+    }
+
+    fn call64_short(function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        smc64_short(function.into(), args)
+    }
+
+    fn call64_short_v1_1(function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        smc64_short_v1_1(function.into(), args)
+    }
+}
+
+/// Wraps another [`Backend`] to set the SMCCC 1.3 hint that the caller has no live SVE state on
+/// every fast call, so that firmware may skip saving and restoring the SVE registers.
+///
+/// For example, `psci::backend::cpu_on(&NoSveLiveState::new(Smc), ...)` makes an SMC with the hint set.
+///
+/// Versions of SMCCC before 1.3 require the hint bit to be zero, so the first call made through
+/// the wrapper queries `SMCCC_VERSION`, and the hint is only set if the firmware implements version
+/// 1.3 or later. The wrapper may therefore safely be used regardless of the SMCCC version.
+pub struct NoSveLiveState<B> {
+    inner: B,
+    /// Whether the firmware supports the hint, or [`HINT_UNKNOWN`] if it hasn't been queried yet.
+    hint: AtomicU8,
+}
+
+const HINT_UNKNOWN: u8 = 0;
+const HINT_SUPPORTED: u8 = 1;
+const HINT_UNSUPPORTED: u8 = 2;
+
+/// The first version of SMCCC which defines the SVE live state hint.
+const SVE_HINT_VERSION: arch::Version = arch::Version { major: 1, minor: 3 };
+
+impl<B> NoSveLiveState<B> {
+    /// Creates a new wrapper around the given backend.
+    pub const fn new(inner: B) -> Self {
+        Self {
+            inner,
+            hint: AtomicU8::new(HINT_UNKNOWN),
+        }
+    }
+
+    /// Returns the wrapped backend.
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B: Backend> NoSveLiveState<B> {
+    /// Returns the given function ID with the hint set if it is a fast call and the firmware
+    /// supports the hint, querying the SMCCC version the first time.
+    fn hinted(&self, function: FunctionId) -> FunctionId {
+        if !function.is_fast() {
+            return function;
+        }
+        let supported = match self.hint.load(Ordering::Relaxed) {
+            HINT_SUPPORTED => true,
+            HINT_UNSUPPORTED => false,
+            _ => {
+                let supported = arch::backend::version(&self.inner)
+                    .is_ok_and(|version| version >= SVE_HINT_VERSION);
+                let hint = if supported {
+                    HINT_SUPPORTED
+                } else {
+                    HINT_UNSUPPORTED
+                };
+                self.hint.store(hint, Ordering::Relaxed);
+                supported
+            }
+        };
+        if supported {
+            function.with_sve_hint()
+        } else {
+            function
+        }
+    }
+}
+
+impl<B: Backend> Backend for NoSveLiveState<B> {
+    fn call32(&self, function: FunctionId, args: [u32; 7]) -> [u32; 8] {
+        self.inner.call32(self.hinted(function), args)
+    }
+
+    fn call32_short(&self, function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        self.inner.call32_short(self.hinted(function), args)
+    }
+
+    fn call32_short_v1_1(&self, function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        self.inner.call32_short_v1_1(self.hinted(function), args)
+    }
+}
+
+impl<B: Backend64> Backend64 for NoSveLiveState<B> {
+    fn call64(&self, function: FunctionId, args: [u64; 17]) -> [u64; 18] {
+        self.inner.call64(self.hinted(function), args)
+    }
+
+    fn call64_short(&self, function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        self.inner.call64_short(self.hinted(function), args)
+    }
+
+    fn call64_short_v1_1(&self, function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        self.inner.call64_short_v1_1(self.hinted(function), args)
+    }
+}
+
+// The HVC and SMC instructions are emitted as raw opcodes on aarch32, so as not to require
+// `-C target-feature=+virtualization,+trustzone`. Thumb code needs the T32 rather than the A32
+// encodings, so the build script sets `cfg(thumb)` for Thumb targets.
+
+/// `hvc #0`, in the A32 encoding.
+#[cfg(all(target_arch = "arm", not(thumb)))]
+macro_rules! hvc0 {
+    () => {
+        ".inst 0xe1400070"
+    };
+}
+
+/// `hvc #0`, in the T32 encoding.
+#[cfg(all(target_arch = "arm", thumb))]
+macro_rules! hvc0 {
+    () => {
+        ".inst.w 0xf7e08000"
+    };
+}
+
+/// `smc #0`, in the A32 encoding.
+#[cfg(all(target_arch = "arm", not(thumb)))]
+macro_rules! smc0 {
+    () => {
+        ".inst 0xe1600070"
+    };
+}
+
+/// `smc #0`, in the T32 encoding.
+#[cfg(all(target_arch = "arm", thumb))]
+macro_rules! smc0 {
+    () => {
+        ".inst.w 0xf7f08000"
+    };
+}
+
 /// Makes an HVC32 call to the hypervisor, following the SMC Calling Convention version 1.4.
 #[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
 #[inline(always)]
@@ -94,7 +434,7 @@ pub fn hvc32(function: u32, args: [u32; 7]) -> [u32; 8] {
             "mov {tmp7}, r7",
             "mov r6, {r6_value}",
             "mov r7, {r7_value}",
-            ".inst 0xe1400070", // hvc #0, without requiring -C target-feature=+virtualization
+            hvc0!(),
             "mov {r6_value}, r6",
             "mov {r7_value}, r7",
             "mov r6, {tmp6}",
@@ -144,7 +484,7 @@ pub fn smc32(function: u32, args: [u32; 7]) -> [u32; 8] {
             "mov {tmp7}, r7",
             "mov r6, {r6_value}",
             "mov r7, {r7_value}",
-            ".inst 0xe1600070", // smc #0, without requiring -C target-feature=+trustzone
+            smc0!(),
             "mov {r6_value}, r6",
             "mov {r7_value}, r7",
             "mov r6, {tmp6}",
@@ -237,3 +577,369 @@ pub fn smc64(function: u32, args: [u64; 17]) -> [u64; 18] {
         ret
     }
 }
+
+/// Makes an HVC32 call to the hypervisor with at most three arguments and four results, following
+/// the register preservation rules of SMC Calling Convention version 1.0.
+#[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
+#[inline(always)]
+pub fn hvc32_short(function: u32, args: [u32; 3]) -> [u32; 4] {
+    // SAFETY: This shouldn't affect our memory, and we mark every register which the callee may
+    // modify as an output or clobber.
+    unsafe {
+        let mut ret = [0; 4];
+
+        #[cfg(target_arch = "aarch64")]
+        core::arch::asm!(
+            "hvc #0",
+            inout("w0") function => ret[0],
+            inout("w1") args[0] => ret[1],
+            inout("w2") args[1] => ret[2],
+            inout("w3") args[2] => ret[3],
+            out("x4") _,
+            out("x5") _,
+            out("x6") _,
+            out("x7") _,
+            out("x8") _,
+            out("x9") _,
+            out("x10") _,
+            out("x11") _,
+            out("x12") _,
+            out("x13") _,
+            out("x14") _,
+            out("x15") _,
+            out("x16") _,
+            out("x17") _,
+            options(nostack)
+        );
+        // r6 and r7 can't be marked as clobbered, so save and restore them instead.
+        #[cfg(target_arch = "arm")]
+        core::arch::asm!(
+            "mov {tmp6}, r6",
+            "mov {tmp7}, r7",
+            hvc0!(),
+            "mov r6, {tmp6}",
+            "mov r7, {tmp7}",
+            tmp6 = out(reg) _,
+            tmp7 = out(reg) _,
+            inout("r0") function => ret[0],
+            inout("r1") args[0] => ret[1],
+            inout("r2") args[1] => ret[2],
+            inout("r3") args[2] => ret[3],
+            out("r4") _,
+            out("r5") _,
+            options(nostack)
+        );
+
+        ret
+    }
+}
+
+/// Makes an HVC32 call to the hypervisor with at most three arguments and four results, relying on
+/// the callee to preserve all other registers as required by SMC Calling Convention version 1.1
+/// and later.
+#[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
+#[inline(always)]
+pub fn hvc32_short_v1_1(function: u32, args: [u32; 3]) -> [u32; 4] {
+    // SAFETY: This shouldn't affect our memory, and we mark every register which the callee may
+    // modify as an output or clobber.
+    unsafe {
+        let mut ret = [0; 4];
+
+        #[cfg(target_arch = "aarch64")]
+        core::arch::asm!(
+            "hvc #0",
+            inout("w0") function => ret[0],
+            inout("w1") args[0] => ret[1],
+            inout("w2") args[1] => ret[2],
+            inout("w3") args[2] => ret[3],
+            options(nostack)
+        );
+        #[cfg(target_arch = "arm")]
+        core::arch::asm!(
+            hvc0!(),
+            inout("r0") function => ret[0],
+            inout("r1") args[0] => ret[1],
+            inout("r2") args[1] => ret[2],
+            inout("r3") args[2] => ret[3],
+            options(nostack)
+        );
+
+        ret
+    }
+}
+
+/// Makes an SMC32 call to the firmware with at most three arguments and four results, following
+/// the register preservation rules of SMC Calling Convention version 1.0.
+#[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
+#[inline(always)]
+pub fn smc32_short(function: u32, args: [u32; 3]) -> [u32; 4] {
+    // SAFETY: This shouldn't affect our memory, and we mark every register which the callee may
+    // modify as an output or clobber.
+    unsafe {
+        let mut ret = [0; 4];
+
+        #[cfg(target_arch = "aarch64")]
+        core::arch::asm!(
+            "smc #0",
+            inout("w0") function => ret[0],
+            inout("w1") args[0] => ret[1],
+            inout("w2") args[1] => ret[2],
+            inout("w3") args[2] => ret[3],
+            out("x4") _,
+            out("x5") _,
+            out("x6") _,
+            out("x7") _,
+            out("x8") _,
+            out("x9") _,
+            out("x10") _,
+            out("x11") _,
+            out("x12") _,
+            out("x13") _,
+            out("x14") _,
+            out("x15") _,
+            out("x16") _,
+            out("x17") _,
+            options(nostack)
+        );
+        // r6 and r7 can't be marked as clobbered, so save and restore them instead.
+        #[cfg(target_arch = "arm")]
+        core::arch::asm!(
+            "mov {tmp6}, r6",
+            "mov {tmp7}, r7",
+            smc0!(),
+            "mov r6, {tmp6}",
+            "mov r7, {tmp7}",
+            tmp6 = out(reg) _,
+            tmp7 = out(reg) _,
+            inout("r0") function => ret[0],
+            inout("r1") args[0] => ret[1],
+            inout("r2") args[1] => ret[2],
+            inout("r3") args[2] => ret[3],
+            out("r4") _,
+            out("r5") _,
+            options(nostack)
+        );
+
+        ret
+    }
+}
+
+/// Makes an SMC32 call to the firmware with at most three arguments and four results, relying on
+/// the callee to preserve all other registers as required by SMC Calling Convention version 1.1
+/// and later.
+#[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
+#[inline(always)]
+pub fn smc32_short_v1_1(function: u32, args: [u32; 3]) -> [u32; 4] {
+    // SAFETY: This shouldn't affect our memory, and we mark every register which the callee may
+    // modify as an output or clobber.
+    unsafe {
+        let mut ret = [0; 4];
+
+        #[cfg(target_arch = "aarch64")]
+        core::arch::asm!(
+            "smc #0",
+            inout("w0") function => ret[0],
+            inout("w1") args[0] => ret[1],
+            inout("w2") args[1] => ret[2],
+            inout("w3") args[2] => ret[3],
+            options(nostack)
+        );
+        #[cfg(target_arch = "arm")]
+        core::arch::asm!(
+            smc0!(),
+            inout("r0") function => ret[0],
+            inout("r1") args[0] => ret[1],
+            inout("r2") args[1] => ret[2],
+            inout("r3") args[2] => ret[3],
+            options(nostack)
+        );
+
+        ret
+    }
+}
+
+/// Makes an HVC64 call to the hypervisor with at most three arguments and four results, following
+/// the register preservation rules of SMC Calling Convention version 1.0.
+#[cfg(target_arch = "aarch64")]
+#[inline(always)]
+pub fn hvc64_short(function: u32, args: [u64; 3]) -> [u64; 4] {
+    // SAFETY: This shouldn't affect our memory, and we mark every register which the callee may
+    // modify as an output or clobber.
+    unsafe {
+        let mut ret = [0; 4];
+
+        core::arch::asm!(
+            "hvc #0",
+            inout("x0") function as u64 => ret[0],
+            inout("x1") args[0] => ret[1],
+            inout("x2") args[1] => ret[2],
+            inout("x3") args[2] => ret[3],
+            out("x4") _,
+            out("x5") _,
+            out("x6") _,
+            out("x7") _,
+            out("x8") _,
+            out("x9") _,
+            out("x10") _,
+            out("x11") _,
+            out("x12") _,
+            out("x13") _,
+            out("x14") _,
+            out("x15") _,
+            out("x16") _,
+            out("x17") _,
+            options(nostack)
+        );
+
+        ret
+    }
+}
+
+/// Makes an HVC64 call to the hypervisor with at most three arguments and four results, relying on
+/// the callee to preserve all other registers as required by SMC Calling Convention version 1.1
+/// and later.
+#[cfg(target_arch = "aarch64")]
+#[inline(always)]
+pub fn hvc64_short_v1_1(function: u32, args: [u64; 3]) -> [u64; 4] {
+    // SAFETY: This shouldn't affect our memory, and we mark every register which the callee may
+    // modify as an output or clobber.
+    unsafe {
+        let mut ret = [0; 4];
+
+        core::arch::asm!(
+            "hvc #0",
+            inout("x0") function as u64 => ret[0],
+            inout("x1") args[0] => ret[1],
+            inout("x2") args[1] => ret[2],
+            inout("x3") args[2] => ret[3],
+            options(nostack)
+        );
+
+        ret
+    }
+}
+
+/// Makes an SMC64 call to the firmware with at most three arguments and four results, following
+/// the register preservation rules of SMC Calling Convention version 1.0.
+#[cfg(target_arch = "aarch64")]
+#[inline(always)]
+pub fn smc64_short(function: u32, args: [u64; 3]) -> [u64; 4] {
+    // SAFETY: This shouldn't affect our memory, and we mark every register which the callee may
+    // modify as an output or clobber.
+    unsafe {
+        let mut ret = [0; 4];
+
+        core::arch::asm!(
+            "smc #0",
+            inout("x0") function as u64 => ret[0],
+            inout("x1") args[0] => ret[1],
+            inout("x2") args[1] => ret[2],
+            inout("x3") args[2] => ret[3],
+            out("x4") _,
+            out("x5") _,
+            out("x6") _,
+            out("x7") _,
+            out("x8") _,
+            out("x9") _,
+            out("x10") _,
+            out("x11") _,
+            out("x12") _,
+            out("x13") _,
+            out("x14") _,
+            out("x15") _,
+            out("x16") _,
+            out("x17") _,
+            options(nostack)
+        );
+
+        ret
+    }
+}
+
+/// Makes an SMC64 call to the firmware with at most three arguments and four results, relying on
+/// the callee to preserve all other registers as required by SMC Calling Convention version 1.1
+/// and later.
+#[cfg(target_arch = "aarch64")]
+#[inline(always)]
+pub fn smc64_short_v1_1(function: u32, args: [u64; 3]) -> [u64; 4] {
+    // SAFETY: This shouldn't affect our memory, and we mark every register which the callee may
+    // modify as an output or clobber.
+    unsafe {
+        let mut ret = [0; 4];
+
+        core::arch::asm!(
+            "smc #0",
+            inout("x0") function as u64 => ret[0],
+            inout("x1") args[0] => ret[1],
+            inout("x2") args[1] => ret[2],
+            inout("x3") args[2] => ret[3],
+            options(nostack)
+        );
+
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{arch::error::NOT_SUPPORTED, mock::Mock};
+    use core::cell::Cell;
+
+    /// A backend which returns a fixed PSCI version and counts how many calls it gets.
+    struct Counting {
+        calls: Cell<usize>,
+    }
+
+    impl Backend for Counting {
+        fn call32(&self, _function: FunctionId, _args: [u32; 7]) -> [u32; 8] {
+            self.calls.set(self.calls.get() + 1);
+            [0x0001_0001, 0, 0, 0, 0, 0, 0, 0]
+        }
+    }
+
+    #[test]
+    fn stateful_backend() {
+        let backend = Counting {
+            calls: Cell::new(0),
+        };
+        let version = psci::Version { major: 1, minor: 1 };
+        assert_eq!(psci::backend::version(&backend), Ok(version));
+        assert_eq!(
+            psci::backend::version(&backend as &dyn Backend),
+            Ok(version)
+        );
+        assert_eq!(backend.calls.get(), 2);
+    }
+
+    #[test]
+    fn sve_hint_set_from_version_1_3() {
+        let conduit = NoSveLiveState::new(Mock);
+        Mock::expect_call32(arch::SMCCC_VERSION, &[], &[0x0001_0003]);
+        Mock::expect_call64(psci::PSCI_CPU_ON_64.with_sve_hint(), &[1, 2, 3], &[0]);
+        assert_eq!(psci::backend::cpu_on(&conduit, 1, 2, 3), Ok(()));
+        Mock::expect_call32(psci::PSCI_CPU_OFF.with_sve_hint(), &[], &[0]);
+        assert_eq!(psci::backend::cpu_off(&conduit), Ok(()));
+        Mock::finish();
+    }
+
+    #[test]
+    fn sve_hint_not_set_before_version_1_3() {
+        let conduit = NoSveLiveState::new(Mock);
+        Mock::expect_call32(arch::SMCCC_VERSION, &[], &[0x0001_0002]);
+        Mock::expect_call32(psci::PSCI_FEATURES, &[0x8400_0001], &[NOT_SUPPORTED as u32]);
+        assert_eq!(
+            psci::backend::psci_features(&conduit, FunctionId::new(0x8400_0001)),
+            Err(psci::Error::NotSupported)
+        );
+        Mock::expect_call32(psci::PSCI_CPU_OFF, &[], &[0]);
+        assert_eq!(psci::backend::cpu_off(&conduit), Ok(()));
+        Mock::finish();
+
+        let conduit = NoSveLiveState::new(Mock);
+        Mock::expect_call32(arch::SMCCC_VERSION, &[], &[NOT_SUPPORTED as u32]);
+        Mock::expect_call32(psci::PSCI_CPU_OFF, &[], &[0]);
+        assert_eq!(psci::backend::cpu_off(&conduit), Ok(()));
+        Mock::finish();
+    }
+}
@@ -0,0 +1,231 @@
+// Copyright 2026 the authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! A wrapper conduit which logs every call made through it, for debugging bring-up.
+
+use crate::{
+    Backend, Backend64, Convention, FunctionId, OwningEntity, arch,
+    function_id::FunctionIdError,
+    psci,
+    registry::{self, FormattedCall, Returns, Spec},
+};
+use core::fmt::{self, Display, Formatter};
+
+/// Wraps another [`Backend`] to log every call made through it at debug level, with the function
+/// name, its arguments and the decoded result.
+///
+/// Each call is logged before it is made, so that calls which don't return such as `CPU_OFF` are
+/// still logged, and then again with its result. For example,
+/// `psci::backend::cpu_on(&Logging::new(Smc), 1, entry, 0)` might log
+/// `PSCI_CPU_ON_64(0x1, 0x80000000, 0x0) ...` followed by
+/// `PSCI_CPU_ON_64(0x1, 0x80000000, 0x0) -> Err(AlreadyOn)`.
+pub struct Logging<B> {
+    inner: B,
+    /// A bitmask of the owning entity numbers for which calls are not logged.
+    silenced: u64,
+}
+
+impl<B> Logging<B> {
+    /// Creates a new wrapper around the given backend, which logs calls to all owning entities.
+    pub const fn new(inner: B) -> Self {
+        Self { inner, silenced: 0 }
+    }
+
+    /// Sets whether to stop logging fast calls to the given owning entity, e.g. to silence
+    /// `SMCCC_ARCH_WORKAROUND_1` on every context switch.
+    ///
+    /// Returns an error if the index of a Trusted Application or Trusted OS is out of range.
+    pub fn set_silenced(
+        &mut self,
+        owning_entity: OwningEntity,
+        silenced: bool,
+    ) -> Result<(), FunctionIdError> {
+        let bit = 1 << owning_entity.number()?;
+        if silenced {
+            self.silenced |= bit;
+        } else {
+            self.silenced &= !bit;
+        }
+        Ok(())
+    }
+
+    /// Returns the wrapped backend.
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+
+    fn is_silenced(&self, function: FunctionId) -> bool {
+        match function.owning_entity().map(OwningEntity::number) {
+            Some(Ok(number)) => self.silenced & 1 << number != 0,
+            _ => false,
+        }
+    }
+
+    /// Logs a call which is about to be made.
+    fn log_call(&self, function: FunctionId, args: &[u64]) {
+        if !self.is_silenced(function) {
+            log::debug!("{} ...", FormattedCall { function, args });
+        }
+    }
+
+    /// Logs a call which has returned, with its result.
+    fn log_result(&self, function: FunctionId, args: &[u64], result: u64) {
+        if !self.is_silenced(function) {
+            log::debug!(
+                "{}",
+                CallRecord {
+                    call: FormattedCall { function, args },
+                    result
+                }
+            );
+        }
+    }
+}
+
+impl<B: Backend> Backend for Logging<B> {
+    fn call32(&self, function: FunctionId, args: [u32; 7]) -> [u32; 8] {
+        let logged_args = args.map(u64::from);
+        self.log_call(function, &logged_args);
+        let ret = self.inner.call32(function, args);
+        self.log_result(function, &logged_args, ret[0].into());
+        ret
+    }
+
+    fn call32_short(&self, function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        let logged_args = args.map(u64::from);
+        self.log_call(function, &logged_args);
+        let ret = self.inner.call32_short(function, args);
+        self.log_result(function, &logged_args, ret[0].into());
+        ret
+    }
+
+    fn call32_short_v1_1(&self, function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        let logged_args = args.map(u64::from);
+        self.log_call(function, &logged_args);
+        let ret = self.inner.call32_short_v1_1(function, args);
+        self.log_result(function, &logged_args, ret[0].into());
+        ret
+    }
+}
+
+impl<B: Backend64> Backend64 for Logging<B> {
+    fn call64(&self, function: FunctionId, args: [u64; 17]) -> [u64; 18] {
+        self.log_call(function, &args);
+        let ret = self.inner.call64(function, args);
+        self.log_result(function, &args, ret[0]);
+        ret
+    }
+
+    fn call64_short(&self, function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        self.log_call(function, &args);
+        let ret = self.inner.call64_short(function, args);
+        self.log_result(function, &args, ret[0]);
+        ret
+    }
+
+    fn call64_short_v1_1(&self, function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        self.log_call(function, &args);
+        let ret = self.inner.call64_short_v1_1(function, args);
+        self.log_result(function, &args, ret[0]);
+        ret
+    }
+}
+
+/// A call and its result, formatted for logging.
+struct CallRecord<'a> {
+    call: FormattedCall<'a>,
+    /// The value returned in x0, or w0 zero-extended.
+    result: u64,
+}
+
+impl Display for CallRecord<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} -> ", self.call)?;
+        let result = match self.call.function.convention() {
+            Convention::Smc32 => (self.result as u32 as i32).into(),
+            Convention::Smc64 => self.result as i64,
+        };
+        match registry::lookup(self.call.function) {
+            Some(info) if result < 0 && info.returns != Returns::Raw => match info.spec {
+                Spec::Smccc(_) => write!(f, "Err({:?})", arch::Error::from(result as i32)),
+                Spec::Psci(_) => write!(f, "Err({:?})", psci::Error::from(result)),
+            },
+            _ if result < 0 => write!(f, "{result}"),
+            _ => write!(f, "{result:#x}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{arch::SMCCC_ARCH_FEATURES, psci::*};
+    use std::string::{String, ToString};
+
+    fn record(function: FunctionId, args: &[u64], result: u64) -> String {
+        CallRecord {
+            call: FormattedCall { function, args },
+            result,
+        }
+        .to_string()
+    }
+
+    #[test]
+    fn format_known_calls() {
+        assert_eq!(
+            record(PSCI_CPU_ON_64, &[1, 0x8000_0000, 0], -4i64 as u64),
+            "PSCI_CPU_ON_64(0x1, 0x80000000, 0x0) -> Err(AlreadyOn)"
+        );
+        assert_eq!(
+            record(PSCI_VERSION, &[0; 7], 0x0001_0001),
+            "PSCI_VERSION() -> 0x10001"
+        );
+        assert_eq!(
+            record(SMCCC_ARCH_FEATURES, &[0x8000_8000, 0, 0], 0xffff_fffe),
+            "SMCCC_ARCH_FEATURES(0x80008000) -> Err(NotRequired)"
+        );
+        assert_eq!(
+            record(PSCI_SYSTEM_OFF2_64, &[1, 0, 0], -3i64 as u64),
+            "PSCI_SYSTEM_OFF2_64(0x1, 0x0) -> Err(Denied)"
+        );
+        assert_eq!(
+            record(PSCI_CPU_OFF.with_sve_hint(), &[0; 3], 0),
+            "PSCI_CPU_OFF[no SVE]() -> 0x0"
+        );
+        // Fewer arguments than the function takes are shown without panicking.
+        assert_eq!(
+            record(PSCI_CPU_ON_64, &[1], 0),
+            "PSCI_CPU_ON_64(0x1) -> 0x0"
+        );
+    }
+
+    #[test]
+    fn format_unknown_calls() {
+        assert_eq!(
+            record(FunctionId::new(0xC200_0042), &[1, 0, 2, 0, 0], -2i64 as u64),
+            "0xc2000042(0x1, 0x0, 0x2) -> -2"
+        );
+    }
+
+    #[test]
+    fn silence_owning_entity() {
+        let mut logging = Logging::new(());
+        logging
+            .set_silenced(OwningEntity::ArmArchitecture, true)
+            .unwrap();
+        logging
+            .set_silenced(OwningEntity::TrustedOs(13), true)
+            .unwrap();
+        assert_eq!(logging.silenced, 1 << 0 | 1 << 63);
+        logging
+            .set_silenced(OwningEntity::ArmArchitecture, false)
+            .unwrap();
+        assert_eq!(logging.silenced, 1 << 63);
+        assert!(
+            logging
+                .set_silenced(OwningEntity::TrustedOs(14), true)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,272 @@
+// Copyright 2026 the authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Implementation details of the [`define_calls!`](crate::define_calls) macro.
+
+use crate::{Call, Call64, FunctionId};
+use core::marker::PhantomData;
+
+/// Defines functions which make SMCCC calls via any [`Backend`](crate::Backend), with typed
+/// arguments and decoded results.
+///
+/// Each definition gives the function signature without the conduit argument, then the
+/// [`Backend`](crate::Backend) or [`Backend64`](crate::Backend64) method and function ID used to
+/// make the call, and how to decode the value returned in w0 or x0:
+///
+/// - `success`: `Ok(())` for `SUCCESS`, or else an error converted from the signed value.
+/// - `positive`: `Ok(value)` if the value is non-negative when treated as signed, or else an error
+///   converted from it.
+/// - `try_from`: the value treated as signed and converted with `TryFrom<i32>` for 32-bit methods or
+///   `TryFrom<i64>` for 64-bit methods, e.g. to an enum.
+/// - `raw`: the value unchanged.
+///
+/// Each argument is converted to a register value with `Into`, and any unused argument registers
+/// are set to zero. It is a compile-time error to give more arguments than the method passes.
+/// Functions using a 64-bit method require the conduit to implement
+/// [`Backend64`](crate::Backend64).
+///
+/// ```
+/// use smccc::{Backend, Backend64, FunctionId, define_calls};
+///
+/// const OEM_SET_FAN_SPEED: FunctionId = FunctionId::new(0x8300_0001);
+/// const OEM_GET_TEMPERATURE: FunctionId = FunctionId::new(0xC300_0002);
+///
+/// #[derive(Debug, PartialEq)]
+/// pub struct Error(i64);
+///
+/// impl From<i32> for Error {
+///     fn from(value: i32) -> Self {
+///         Self(value.into())
+///     }
+/// }
+///
+/// impl From<i64> for Error {
+///     fn from(value: i64) -> Self {
+///         Self(value)
+///     }
+/// }
+///
+/// define_calls! {
+///     /// Sets the speed of the given fan, as a percentage.
+///     pub fn set_fan_speed(fan: u32, percent: u32) -> Result<(), Error> =
+///         call32_short_v1_1(OEM_SET_FAN_SPEED) => success;
+///
+///     /// Returns the temperature of the given sensor in millidegrees Celsius.
+///     pub fn temperature(sensor: u32) -> Result<u64, Error> =
+///         call64_short_v1_1(OEM_GET_TEMPERATURE) => positive;
+/// }
+///
+/// /// A fake firmware which implements both functions.
+/// struct Firmware;
+///
+/// impl Backend for Firmware {
+///     fn call32(&self, function: FunctionId, args: [u32; 7]) -> [u32; 8] {
+///         assert_eq!(function, OEM_SET_FAN_SPEED);
+///         let ret = if args[1] <= 100 { 0 } else { -2i32 as u32 };
+///         [ret, 0, 0, 0, 0, 0, 0, 0]
+///     }
+/// }
+///
+/// impl Backend64 for Firmware {
+///     fn call64(&self, function: FunctionId, args: [u64; 17]) -> [u64; 18] {
+///         assert_eq!(function, OEM_GET_TEMPERATURE);
+///         let mut ret = [0; 18];
+///         ret[0] = if args[0] == 0 { 42_000 } else { -2i64 as u64 };
+///         ret
+///     }
+/// }
+///
+/// assert_eq!(set_fan_speed(&Firmware, 1, 50), Ok(()));
+/// assert_eq!(set_fan_speed(&Firmware, 1, 150), Err(Error(-2)));
+/// assert_eq!(temperature(&Firmware, 0), Ok(42_000));
+/// assert_eq!(temperature(&Firmware, 1), Err(Error(-2)));
+/// ```
+///
+/// Giving more arguments than the method passes is an error:
+///
+/// ```compile_fail
+/// use smccc::{FunctionId, define_calls};
+///
+/// define_calls! {
+///     pub fn too_many(a: u32, b: u32, c: u32, d: u32) -> u32 =
+///         call32_short(FunctionId::new(0x8300_0001)) => raw;
+/// }
+/// ```
+#[macro_export]
+macro_rules! define_calls {
+    ($(
+        $(#[$attr:meta])*
+        $vis:vis fn $name:ident($($arg:ident: $arg_type:ty),* $(,)?) -> $ret:ty =
+            $method:ident($function:expr) => $decode:ident;
+    )*) => {$(
+        $crate::define_calls!(
+            @method $method [$(#[$attr])*] $vis fn $name($($arg: $arg_type),*) -> $ret =
+                ($function) => $decode
+        );
+    )*};
+    (@method call32 $($rest:tt)*) => {
+        $crate::define_calls!(@define $crate::Backend, 7, call32 $($rest)*);
+    };
+    (@method call32_short $($rest:tt)*) => {
+        $crate::define_calls!(@define $crate::Backend, 3, call32_short $($rest)*);
+    };
+    (@method call32_short_v1_1 $($rest:tt)*) => {
+        $crate::define_calls!(@define $crate::Backend, 3, call32_short_v1_1 $($rest)*);
+    };
+    (@method call64 $($rest:tt)*) => {
+        $crate::define_calls!(@define $crate::Backend64, 17, call64 $($rest)*);
+    };
+    (@method call64_short $($rest:tt)*) => {
+        $crate::define_calls!(@define $crate::Backend64, 3, call64_short $($rest)*);
+    };
+    (@method call64_short_v1_1 $($rest:tt)*) => {
+        $crate::define_calls!(@define $crate::Backend64, 3, call64_short_v1_1 $($rest)*);
+    };
+    (
+        @define $bound:path, $max_args:literal, $method:ident [$(#[$attr:meta])*]
+        $vis:vis fn $name:ident($($arg:ident: $arg_type:ty),*) -> $ret:ty =
+            ($function:expr) => $decode:ident
+    ) => {
+        const _: () = assert!(
+            <[&str]>::len(&[$(stringify!($arg)),*]) <= $max_args,
+            concat!("Too many arguments for `", stringify!($method), "` in `", stringify!($name), "`"),
+        );
+        $(#[$attr])*
+        $vis fn $name<C: $bound + ?Sized>(conduit: &C, $($arg: $arg_type),*) -> $ret {
+            let ret = conduit.$method(
+                $function,
+                $crate::macros::pad([$(::core::convert::Into::into($arg)),*]),
+            );
+            $crate::define_calls!(@decode $decode, ret[0])
+        }
+    };
+    (@decode success, $value:expr) => {
+        $crate::macros::success_or_error($value)
+    };
+    (@decode positive, $value:expr) => {
+        $crate::macros::positive_or_error($value)
+    };
+    (@decode try_from, $value:expr) => {
+        ::core::convert::TryInto::try_into($crate::macros::Register::signed($value))
+    };
+    (@decode raw, $value:expr) => {
+        $value
+    };
+}
+
+/// Defines functions which take a [`Call`](crate::Call) or [`Call64`](crate::Call64) type as a
+/// type parameter and forward to the functions of the same name in the given module, which take a
+/// [`Backend`](crate::Backend) instance.
+macro_rules! define_static_calls {
+    (
+        $module:ident;
+        $(
+            $(#[$attr:meta])*
+            $vis:vis fn $name:ident<C: $bound:ident>($($arg:ident: $arg_type:ty),* $(,)?) -> $ret:ty;
+        )*
+    ) => {$(
+        $(#[$attr])*
+        $vis fn $name<C: $crate::$bound>($($arg: $arg_type),*) -> $ret {
+            $module::$name(&$crate::macros::StaticCall::<C>::new(), $($arg),*)
+        }
+    )*};
+}
+pub(crate) use define_static_calls;
+
+/// Adapts a [`Call`] or [`Call64`] type to an instance, so that it can be passed to functions
+/// taking a [`Backend`](crate::Backend).
+pub(crate) struct StaticCall<C>(PhantomData<C>);
+
+impl<C> StaticCall<C> {
+    pub(crate) const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<C: Call> Call for StaticCall<C> {
+    fn call32(function: FunctionId, args: [u32; 7]) -> [u32; 8] {
+        C::call32(function, args)
+    }
+
+    fn call32_short(function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        C::call32_short(function, args)
+    }
+
+    fn call32_short_v1_1(function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        C::call32_short_v1_1(function, args)
+    }
+}
+
+impl<C: Call64> Call64 for StaticCall<C> {
+    fn call64(function: FunctionId, args: [u64; 17]) -> [u64; 18] {
+        C::call64(function, args)
+    }
+
+    fn call64_short(function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        C::call64_short(function, args)
+    }
+
+    fn call64_short_v1_1(function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        C::call64_short_v1_1(function, args)
+    }
+}
+
+/// A register value for the 32-bit or 64-bit calling convention.
+#[doc(hidden)]
+pub trait Register: Copy + Default {
+    /// The register value treated as a signed integer.
+    type Signed: Copy + Default + PartialOrd;
+
+    /// Reinterprets the register value as signed.
+    fn signed(self) -> Self::Signed;
+}
+
+impl Register for u32 {
+    type Signed = i32;
+
+    fn signed(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Register for u64 {
+    type Signed = i64;
+
+    fn signed(self) -> i64 {
+        self as i64
+    }
+}
+
+/// Returns the given arguments followed by zeroes, to fill an argument array.
+///
+/// Fails to compile if there are more arguments than fit in the array.
+#[doc(hidden)]
+pub fn pad<R: Register, const M: usize, const N: usize>(args: [R; M]) -> [R; N] {
+    const { assert!(M <= N, "Too many arguments for the call") };
+    core::array::from_fn(|i| args.get(i).copied().unwrap_or_default())
+}
+
+/// Like [`success_or_error_32`](crate::error::success_or_error_32) or
+/// [`success_or_error_64`](crate::error::success_or_error_64), for either register width.
+#[doc(hidden)]
+pub fn success_or_error<R: Register, E: From<R::Signed>>(value: R) -> Result<(), E> {
+    let signed = value.signed();
+    if signed == R::Signed::default() {
+        Ok(())
+    } else {
+        Err(signed.into())
+    }
+}
+
+/// Like [`positive_or_error_32`](crate::error::positive_or_error_32) or
+/// [`positive_or_error_64`](crate::error::positive_or_error_64), for either register width.
+#[doc(hidden)]
+pub fn positive_or_error<R: Register, E: From<R::Signed>>(value: R) -> Result<R, E> {
+    let signed = value.signed();
+    if signed < R::Signed::default() {
+        Err(signed.into())
+    } else {
+        Ok(value)
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright 2026 the authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! A scriptable [`Call`] implementation for testing code which makes SMCCC calls, on any host.
+//!
+//! Tests queue the calls they expect to be made, along with the values to return, and then check
+//! that all of them were made. For example:
+//!
+//! ```
+//! use smccc::{mock::Mock, psci};
+//!
+//! Mock::expect_call64(psci::PSCI_CPU_ON_64, &[1, 0x8000_0000, 42], &[0]);
+//! assert_eq!(psci::cpu_on::<Mock>(1, 0x8000_0000, 42), Ok(()));
+//! Mock::finish();
+//! ```
+//!
+//! Expectations are kept per thread, so tests running in parallel don't interfere with each other.
+
+use crate::{Call, Call64, FunctionId};
+use std::{cell::RefCell, collections::VecDeque, thread_local, vec::Vec};
+
+thread_local! {
+    static EXPECTATIONS: RefCell<VecDeque<Expectation>> = const { RefCell::new(VecDeque::new()) };
+}
+
+/// A call which is expected to be made, and the value to return from it.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Expectation {
+    /// A call using the 32-bit calling convention.
+    Call32 {
+        function: FunctionId,
+        args: [u32; 7],
+        ret: [u32; 8],
+    },
+    /// A call using the 64-bit calling convention.
+    Call64 {
+        function: FunctionId,
+        args: [u64; 17],
+        ret: [u64; 18],
+    },
+}
+
+/// A mock conduit which checks calls against the expectations queued on the current thread.
+///
+/// Calls panic if they don't match the next expectation, or if there are no expectations left.
+pub struct Mock;
+
+impl Mock {
+    /// Expects a call using the 32-bit calling convention with the given function ID and arguments,
+    /// and returns the given values from it.
+    ///
+    /// Any arguments or return values not given are zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than 7 arguments or 8 return values are given.
+    pub fn expect_call32(function: FunctionId, args: &[u32], ret: &[u32]) {
+        push(Expectation::Call32 {
+            function,
+            args: pad(args),
+            ret: pad(ret),
+        });
+    }
+
+    /// Expects a call using the 64-bit calling convention with the given function ID and arguments,
+    /// and returns the given values from it.
+    ///
+    /// Any arguments or return values not given are zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than 17 arguments or 18 return values are given.
+    pub fn expect_call64(function: FunctionId, args: &[u64], ret: &[u64]) {
+        push(Expectation::Call64 {
+            function,
+            args: pad(args),
+            ret: pad(ret),
+        });
+    }
+
+    /// Removes and returns all expectations on the current thread which have not yet been consumed.
+    pub fn take_unconsumed() -> Vec<Expectation> {
+        EXPECTATIONS.with_borrow_mut(|expectations| expectations.drain(..).collect())
+    }
+
+    /// Checks that all expected calls on the current thread have been made.
+    ///
+    /// # Panics
+    ///
+    /// Panics with a list of the expectations which were never consumed, if there are any.
+    pub fn finish() {
+        let unconsumed = Self::take_unconsumed();
+        assert!(
+            unconsumed.is_empty(),
+            "{} expected calls were never made: {:#?}",
+            unconsumed.len(),
+            unconsumed
+        );
+    }
+}
+
+impl Call for Mock {
+    fn call32(function: FunctionId, args: [u32; 7]) -> [u32; 8] {
+        match pop() {
+            Some(Expectation::Call32 {
+                function: expected_function,
+                args: expected_args,
+                ret,
+            }) if expected_function == function && expected_args == args => ret,
+            expectation => panic!(
+                "Unexpected call32 to {function:?} with args {args:#x?}, expected {expectation:#x?}"
+            ),
+        }
+    }
+}
+
+impl Call64 for Mock {
+    fn call64(function: FunctionId, args: [u64; 17]) -> [u64; 18] {
+        match pop() {
+            Some(Expectation::Call64 {
+                function: expected_function,
+                args: expected_args,
+                ret,
+            }) if expected_function == function && expected_args == args => ret,
+            expectation => panic!(
+                "Unexpected call64 to {function:?} with args {args:#x?}, expected {expectation:#x?}"
+            ),
+        }
+    }
+}
+
+fn push(expectation: Expectation) {
+    EXPECTATIONS.with_borrow_mut(|expectations| expectations.push_back(expectation));
+}
+
+fn pop() -> Option<Expectation> {
+    EXPECTATIONS.with_borrow_mut(|expectations| expectations.pop_front())
+}
+
+fn pad<T: Copy + Default, const N: usize>(values: &[T]) -> [T; N] {
+    assert!(values.len() <= N, "Too many values given, maximum is {N}");
+    let mut padded = [T::default(); N];
+    padded[..values.len()].copy_from_slice(values);
+    padded
+}
@@ -0,0 +1,388 @@
+// Copyright 2026 the authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! A wrapper conduit which measures how long each function takes, without allocating.
+//!
+//! For example, to time PSCI and arch calls made via SMC with the generic timer:
+//!
+//! ```
+//! use core::fmt::{self, Write};
+//! use smccc::{
+//!     Backend,
+//!     profiling::{Clock, Profiling},
+//! };
+//! # #[cfg(target_arch = "aarch64")]
+//! use smccc::{Smc, profiling::GenericTimer, psci};
+//!
+//! # #[cfg(target_arch = "aarch64")]
+//! static PROFILING: Profiling<Smc, GenericTimer, 16> = Profiling::new(Smc, GenericTimer);
+//!
+//! # #[cfg(target_arch = "aarch64")]
+//! # fn suspend(power_state: u32, entry_point: u64) -> Result<(), psci::Error> {
+//! psci::backend::cpu_suspend(&PROFILING, power_state, entry_point, 0)
+//! # }
+//!
+//! /// Writes the statistics for each function called via the given wrapper.
+//! fn report<B: Backend, C: Clock, const N: usize>(
+//!     profiling: &Profiling<B, C, N>,
+//!     out: &mut impl Write,
+//! ) -> fmt::Result {
+//!     for stats in profiling.snapshot().iter().flatten() {
+//!         let (function, count, mean) = (stats.function, stats.count, stats.mean());
+//!         writeln!(out, "{function}: {count} calls, mean {mean} ticks")?;
+//!         for (bucket, count) in stats.histogram.iter().enumerate() {
+//!             writeln!(out, "  < {} ticks: {count}", 1u128 << bucket)?;
+//!         }
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{Backend, Backend64, FunctionId};
+use core::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+
+/// The raw function ID value used to mark an unused slot. This has reserved bits set, so is never a
+/// well-formed function ID.
+const EMPTY: u32 = u32::MAX;
+
+/// The number of buckets in the latency histogram of each function.
+pub const HISTOGRAM_BUCKETS: usize = 32;
+
+/// Returns the index of the histogram bucket which counts calls taking the given number of ticks.
+///
+/// Bucket 0 counts calls which took 0 ticks, and bucket `i` counts calls which took at least
+/// 2<sup>i-1</sup> and less than 2<sup>i</sup> ticks. The last bucket also counts all longer
+/// calls.
+pub fn histogram_bucket(duration: u64) -> usize {
+    (u64::BITS - duration.leading_zeros()).min(HISTOGRAM_BUCKETS as u32 - 1) as usize
+}
+
+/// A source of timestamps for [`Profiling`].
+pub trait Clock {
+    /// Returns the current time, in arbitrary ticks which increase monotonically.
+    fn now(&self) -> u64;
+}
+
+/// Reads timestamps from the virtual count of the Arm generic timer, `CNTVCT`.
+#[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
+pub struct GenericTimer;
+
+#[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
+impl GenericTimer {
+    /// Returns the frequency of the generic timer in Hz, from `CNTFRQ`.
+    pub fn frequency() -> u32 {
+        // SAFETY: Reading CNTFRQ has no side effects.
+        unsafe {
+            #[cfg(target_arch = "aarch64")]
+            {
+                let frequency: u64;
+                core::arch::asm!(
+                    "mrs {}, cntfrq_el0",
+                    out(reg) frequency,
+                    options(nomem, nostack, preserves_flags)
+                );
+                // The upper 32 bits of CNTFRQ_EL0 are reserved.
+                frequency as u32
+            }
+            #[cfg(target_arch = "arm")]
+            {
+                let frequency: u32;
+                core::arch::asm!(
+                    "mrc p15, 0, {}, c14, c0, 0",
+                    out(reg) frequency,
+                    options(nomem, nostack, preserves_flags)
+                );
+                frequency
+            }
+        }
+    }
+}
+
+#[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
+impl Clock for GenericTimer {
+    fn now(&self) -> u64 {
+        // SAFETY: Reading CNTVCT has no side effects. The ISB ensures that the counter isn't read
+        // early, before the preceding call has completed.
+        unsafe {
+            #[cfg(target_arch = "aarch64")]
+            {
+                let count: u64;
+                core::arch::asm!(
+                    "isb",
+                    "mrs {}, cntvct_el0",
+                    out(reg) count,
+                    options(nomem, nostack, preserves_flags)
+                );
+                count
+            }
+            #[cfg(target_arch = "arm")]
+            {
+                let low: u32;
+                let high: u32;
+                core::arch::asm!(
+                    "isb",
+                    "mrrc p15, 1, {}, {}, c14",
+                    out(reg) low,
+                    out(reg) high,
+                    options(nomem, nostack, preserves_flags)
+                );
+                u64::from(high) << 32 | u64::from(low)
+            }
+        }
+    }
+}
+
+/// Timing statistics for one function.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Stats {
+    /// The function ID which was called.
+    pub function: FunctionId,
+    /// The number of calls made.
+    pub count: u64,
+    /// The shortest time taken by a call, in clock ticks.
+    pub min: u64,
+    /// The longest time taken by a call, in clock ticks.
+    pub max: u64,
+    /// The total time taken by all calls, in clock ticks.
+    pub total: u64,
+    /// The number of calls in each latency bucket, as defined by [`histogram_bucket`].
+    pub histogram: [u64; HISTOGRAM_BUCKETS],
+}
+
+impl Stats {
+    /// Returns the mean time taken by a call, in clock ticks.
+    pub fn mean(&self) -> u64 {
+        self.total.checked_div(self.count).unwrap_or(0)
+    }
+}
+
+/// The statistics for one function ID, which may be updated concurrently from several cores.
+struct Slot {
+    function: AtomicU32,
+    count: AtomicU64,
+    min: AtomicU64,
+    max: AtomicU64,
+    total: AtomicU64,
+    histogram: [AtomicU64; HISTOGRAM_BUCKETS],
+}
+
+impl Slot {
+    const fn new() -> Self {
+        Self {
+            function: AtomicU32::new(EMPTY),
+            count: AtomicU64::new(0),
+            min: AtomicU64::new(u64::MAX),
+            max: AtomicU64::new(0),
+            total: AtomicU64::new(0),
+            histogram: [const { AtomicU64::new(0) }; HISTOGRAM_BUCKETS],
+        }
+    }
+
+    fn record(&self, duration: u64) {
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.min.fetch_min(duration, Ordering::Relaxed);
+        self.max.fetch_max(duration, Ordering::Relaxed);
+        self.total.fetch_add(duration, Ordering::Relaxed);
+        self.histogram[histogram_bucket(duration)].fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn stats(&self) -> Option<Stats> {
+        // An empty slot doesn't contain a well-formed function ID.
+        let function = FunctionId::from_raw(self.function.load(Ordering::Acquire)).ok()?;
+        Some(Stats {
+            function,
+            count: self.count.load(Ordering::Relaxed),
+            min: self.min.load(Ordering::Relaxed),
+            max: self.max.load(Ordering::Relaxed),
+            total: self.total.load(Ordering::Relaxed),
+            histogram: core::array::from_fn(|i| self.histogram[i].load(Ordering::Relaxed)),
+        })
+    }
+}
+
+/// Wraps another [`Backend`] to time every call made through it with the given [`Clock`], keeping
+/// statistics for up to `N` different function IDs.
+///
+/// Calls to further function IDs once all `N` slots are in use are counted by
+/// [`Profiling::dropped`] but otherwise not recorded. Statistics are kept with atomics, so a
+/// single instance may be shared between cores.
+pub struct Profiling<B, C, const N: usize> {
+    inner: B,
+    clock: C,
+    slots: [Slot; N],
+    dropped: AtomicU64,
+}
+
+impl<B, C, const N: usize> Profiling<B, C, N> {
+    /// Creates a new wrapper around the given backend, with no calls recorded.
+    pub const fn new(inner: B, clock: C) -> Self {
+        Self {
+            inner,
+            clock,
+            slots: [const { Slot::new() }; N],
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns a copy of the statistics recorded so far, in the order that each function was first
+    /// called, followed by `None` for any unused slots.
+    ///
+    /// Each value is read separately, so if calls are being recorded concurrently on other cores
+    /// then the statistics for a function may be inconsistent, e.g. `total` may include a call
+    /// which `count` doesn't yet. Take the snapshot while no calls are in progress for exact
+    /// figures.
+    pub fn snapshot(&self) -> [Option<Stats>; N] {
+        core::array::from_fn(|i| self.slots[i].stats())
+    }
+
+    /// Returns the number of calls which weren't recorded because all slots were in use.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Returns the slot for the given function, claiming an empty one if necessary.
+    fn slot(&self, function: FunctionId) -> Option<&Slot> {
+        let function = u32::from(function);
+        self.slots.iter().find(|slot| {
+            match slot.function.compare_exchange(
+                EMPTY,
+                function,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => true,
+                Err(existing) => existing == function,
+            }
+        })
+    }
+}
+
+impl<B: Backend, C: Clock, const N: usize> Profiling<B, C, N> {
+    fn timed<R>(&self, function: FunctionId, call: impl FnOnce(&B) -> R) -> R {
+        let start = self.clock.now();
+        let ret = call(&self.inner);
+        let duration = self.clock.now().wrapping_sub(start);
+        if let Some(slot) = self.slot(function) {
+            slot.record(duration);
+        } else {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        ret
+    }
+}
+
+impl<B: Backend, C: Clock, const N: usize> Backend for Profiling<B, C, N> {
+    fn call32(&self, function: FunctionId, args: [u32; 7]) -> [u32; 8] {
+        self.timed(function, |inner| inner.call32(function, args))
+    }
+
+    fn call32_short(&self, function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        self.timed(function, |inner| inner.call32_short(function, args))
+    }
+
+    fn call32_short_v1_1(&self, function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        self.timed(function, |inner| inner.call32_short_v1_1(function, args))
+    }
+}
+
+impl<B: Backend64, C: Clock, const N: usize> Backend64 for Profiling<B, C, N> {
+    fn call64(&self, function: FunctionId, args: [u64; 17]) -> [u64; 18] {
+        self.timed(function, |inner| inner.call64(function, args))
+    }
+
+    fn call64_short(&self, function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        self.timed(function, |inner| inner.call64_short(function, args))
+    }
+
+    fn call64_short_v1_1(&self, function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        self.timed(function, |inner| inner.call64_short_v1_1(function, args))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{arch, mock::Mock, psci};
+    use core::cell::Cell;
+
+    /// A clock which advances by a scripted amount each time it is read.
+    struct FakeClock {
+        now: Cell<u64>,
+        steps: Cell<&'static [u64]>,
+    }
+
+    impl FakeClock {
+        fn new(steps: &'static [u64]) -> Self {
+            Self {
+                now: Cell::new(0),
+                steps: Cell::new(steps),
+            }
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> u64 {
+            if let Some((step, rest)) = self.steps.get().split_first() {
+                self.now.set(self.now.get() + step);
+                self.steps.set(rest);
+            }
+            self.now.get()
+        }
+    }
+
+    #[test]
+    fn records_stats_per_function() {
+        // Calls take 10, 30 and 5 ticks.
+        let profiling =
+            Profiling::<_, _, 2>::new(Mock, FakeClock::new(&[0, 10, 100, 30, 100, 5, 100, 1]));
+        for _ in 0..2 {
+            Mock::expect_call32(arch::SMCCC_ARCH_WORKAROUND_1, &[], &[0]);
+            assert_eq!(arch::backend::arch_workaround_1(&profiling), Ok(()));
+        }
+        Mock::expect_call32(psci::PSCI_CPU_OFF, &[], &[0]);
+        assert_eq!(psci::backend::cpu_off(&profiling), Ok(()));
+        // There is no slot left for this one.
+        Mock::expect_call32(psci::PSCI_SYSTEM_OFF, &[], &[0]);
+        assert_eq!(psci::backend::system_off(&profiling), Ok(()));
+        Mock::finish();
+
+        let mut histogram = [0; HISTOGRAM_BUCKETS];
+        histogram[4] = 1;
+        histogram[5] = 1;
+        let workaround = Stats {
+            function: arch::SMCCC_ARCH_WORKAROUND_1,
+            count: 2,
+            min: 10,
+            max: 30,
+            total: 40,
+            histogram,
+        };
+        assert_eq!(workaround.mean(), 20);
+        assert_eq!(
+            profiling.snapshot(),
+            [
+                Some(workaround),
+                Some(Stats {
+                    function: psci::PSCI_CPU_OFF,
+                    count: 1,
+                    min: 5,
+                    max: 5,
+                    total: 5,
+                    histogram: core::array::from_fn(|i| u64::from(i == 3)),
+                }),
+            ]
+        );
+        assert_eq!(profiling.dropped(), 1);
+    }
+
+    #[test]
+    fn histogram_buckets() {
+        assert_eq!(histogram_bucket(0), 0);
+        assert_eq!(histogram_bucket(1), 1);
+        assert_eq!(histogram_bucket(2), 2);
+        assert_eq!(histogram_bucket(3), 2);
+        assert_eq!(histogram_bucket(1 << 30), 31);
+        assert_eq!(histogram_bucket(u64::MAX), 31);
+    }
+}
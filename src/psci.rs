@@ -2,64 +2,79 @@
 // This project is dual-licensed under Apache 2.0 and MIT terms.
 // See LICENSE-APACHE and LICENSE-MIT for details.
 
-//! Constants for version 1.1 of the Arm Power State Coordination Interface (PSCI) version 1.1, and
-//! functions to call them.
+//! Constants for version 1.3 of the Arm Power State Coordination Interface (PSCI), and functions
+//! to call them.
 //!
 //! Note that PSCI and other SMCCC calls may be made via either HVC or SMC. You can choose which one
-//! to use by building this crate with the corresponding feature (i.e. `hvc` or `smc`). By default
-//! `hvc` is enabled. If neither feature is enabled then the functions to make calls will not be
-//! available, but the constants and types are still provided.
+//! to use by passing either `Hvc` or `Smc` as a type parameter to the relevant function. The
+//! functions in [`backend`] instead take a reference to any implementation of the
+//! [`Backend`](crate::Backend) trait.
 //!
-//! This crate currently only supports aarch64 and the SMC64 versions of the various calls, in the
-//! cases that both SMC32 and SMC64 versions exist.
+//! Where both SMC32 and SMC64 versions of a function exist, the unsuffixed function uses the SMC64
+//! version and the `_32` function the SMC32 version. [`native::Native`] instead takes native width
+//! arguments and picks the version to match the target.
 
+pub mod acpi;
+pub mod backend;
 mod calls;
 pub mod error;
+pub mod fdt;
+mod features;
+pub mod native;
+mod power_state;
+mod request;
 
+use crate::FunctionId;
 pub use calls::{
     affinity_info, affinity_info_32, cpu_default_suspend, cpu_default_suspend_32, cpu_freeze,
-    cpu_off, cpu_on, cpu_on_32, cpu_suspend, cpu_suspend_32, mem_protect, mem_protect_check_range,
-    mem_protect_check_range_32, migrate, migrate_32, migrate_info_type, migrate_info_up_cpu,
-    migrate_info_up_cpu_32, node_hw_state, node_hw_state_32, psci_features, set_suspend_mode,
-    stat_count, stat_count_32, stat_residency, stat_residency_32, system_off, system_reset,
-    system_reset2, system_reset2_32, system_suspend, system_suspend_32, version,
+    cpu_off, cpu_on, cpu_on_32, cpu_suspend, cpu_suspend_32, function_features, mem_protect,
+    mem_protect_check_range, mem_protect_check_range_32, migrate, migrate_32, migrate_info_type,
+    migrate_info_up_cpu, migrate_info_up_cpu_32, node_hw_state, node_hw_state_32,
+    power_state_format, psci_features, set_suspend_mode, stat_count, stat_count_32, stat_residency,
+    stat_residency_32, system_off, system_off2, system_off2_32, system_reset, system_reset2,
+    system_reset2_32, system_suspend, system_suspend_32, version,
 };
 use core::fmt::{self, Debug, Display, Formatter};
 pub use error::Error;
+pub use features::{CpuSuspendFeatures, Features, SystemOff2Features};
+pub use power_state::{PowerStateFormat, PowerStateParam, StateType};
+pub use request::{MismatchedResponse, Request, Response};
 
-pub const PSCI_VERSION: u32 = 0x84000000;
-pub const PSCI_CPU_SUSPEND_32: u32 = 0x84000001;
-pub const PSCI_CPU_SUSPEND_64: u32 = 0xC4000001;
-pub const PSCI_CPU_OFF: u32 = 0x84000002;
-pub const PSCI_CPU_ON_32: u32 = 0x84000003;
-pub const PSCI_CPU_ON_64: u32 = 0xC4000003;
-pub const PSCI_AFFINITY_INFO_32: u32 = 0x84000004;
-pub const PSCI_AFFINITY_INFO_64: u32 = 0xC4000004;
-pub const PSCI_MIGRATE_32: u32 = 0x84000005;
-pub const PSCI_MIGRATE_64: u32 = 0xC4000005;
-pub const PSCI_MIGRATE_INFO_TYPE: u32 = 0x84000006;
-pub const PSCI_MIGRATE_INFO_UP_CPU_32: u32 = 0x84000007;
-pub const PSCI_MIGRATE_INFO_UP_CPU_64: u32 = 0xC4000007;
-pub const PSCI_SYSTEM_OFF: u32 = 0x84000008;
-pub const PSCI_SYSTEM_RESET: u32 = 0x84000009;
-pub const PSCI_SYSTEM_RESET2_32: u32 = 0x84000012;
-pub const PSCI_SYSTEM_RESET2_64: u32 = 0xC4000012;
-pub const PSCI_MEM_PROTECT: u32 = 0x84000013;
-pub const PSCI_MEM_PROTECT_CHECK_RANGE_32: u32 = 0x84000014;
-pub const PSCI_MEM_PROTECT_CHECK_RANGE_64: u32 = 0xC4000014;
-pub const PSCI_FEATURES: u32 = 0x8400000A;
-pub const PSCI_CPU_FREEZE: u32 = 0x8400000B;
-pub const PSCI_CPU_DEFAULT_SUSPEND_32: u32 = 0x8400000C;
-pub const PSCI_CPU_DEFAULT_SUSPEND_64: u32 = 0xC400000C;
-pub const PSCI_NODE_HW_STATE_32: u32 = 0x8400000D;
-pub const PSCI_NODE_HW_STATE_64: u32 = 0xC400000D;
-pub const PSCI_SYSTEM_SUSPEND_32: u32 = 0x8400000E;
-pub const PSCI_SYSTEM_SUSPEND_64: u32 = 0xC400000E;
-pub const PSCI_SET_SUSPEND_MODE: u32 = 0x8400000F;
-pub const PSCI_STAT_RESIDENCY_32: u32 = 0x84000010;
-pub const PSCI_STAT_RESIDENCY_64: u32 = 0xC4000010;
-pub const PSCI_STAT_COUNT_32: u32 = 0x84000011;
-pub const PSCI_STAT_COUNT_64: u32 = 0xC4000011;
+pub const PSCI_VERSION: FunctionId = FunctionId::new(0x84000000);
+pub const PSCI_CPU_SUSPEND_32: FunctionId = FunctionId::new(0x84000001);
+pub const PSCI_CPU_SUSPEND_64: FunctionId = FunctionId::new(0xC4000001);
+pub const PSCI_CPU_OFF: FunctionId = FunctionId::new(0x84000002);
+pub const PSCI_CPU_ON_32: FunctionId = FunctionId::new(0x84000003);
+pub const PSCI_CPU_ON_64: FunctionId = FunctionId::new(0xC4000003);
+pub const PSCI_AFFINITY_INFO_32: FunctionId = FunctionId::new(0x84000004);
+pub const PSCI_AFFINITY_INFO_64: FunctionId = FunctionId::new(0xC4000004);
+pub const PSCI_MIGRATE_32: FunctionId = FunctionId::new(0x84000005);
+pub const PSCI_MIGRATE_64: FunctionId = FunctionId::new(0xC4000005);
+pub const PSCI_MIGRATE_INFO_TYPE: FunctionId = FunctionId::new(0x84000006);
+pub const PSCI_MIGRATE_INFO_UP_CPU_32: FunctionId = FunctionId::new(0x84000007);
+pub const PSCI_MIGRATE_INFO_UP_CPU_64: FunctionId = FunctionId::new(0xC4000007);
+pub const PSCI_SYSTEM_OFF: FunctionId = FunctionId::new(0x84000008);
+pub const PSCI_SYSTEM_RESET: FunctionId = FunctionId::new(0x84000009);
+pub const PSCI_SYSTEM_RESET2_32: FunctionId = FunctionId::new(0x84000012);
+pub const PSCI_SYSTEM_RESET2_64: FunctionId = FunctionId::new(0xC4000012);
+pub const PSCI_SYSTEM_OFF2_32: FunctionId = FunctionId::new(0x84000015);
+pub const PSCI_SYSTEM_OFF2_64: FunctionId = FunctionId::new(0xC4000015);
+pub const PSCI_MEM_PROTECT: FunctionId = FunctionId::new(0x84000013);
+pub const PSCI_MEM_PROTECT_CHECK_RANGE_32: FunctionId = FunctionId::new(0x84000014);
+pub const PSCI_MEM_PROTECT_CHECK_RANGE_64: FunctionId = FunctionId::new(0xC4000014);
+pub const PSCI_FEATURES: FunctionId = FunctionId::new(0x8400000A);
+pub const PSCI_CPU_FREEZE: FunctionId = FunctionId::new(0x8400000B);
+pub const PSCI_CPU_DEFAULT_SUSPEND_32: FunctionId = FunctionId::new(0x8400000C);
+pub const PSCI_CPU_DEFAULT_SUSPEND_64: FunctionId = FunctionId::new(0xC400000C);
+pub const PSCI_NODE_HW_STATE_32: FunctionId = FunctionId::new(0x8400000D);
+pub const PSCI_NODE_HW_STATE_64: FunctionId = FunctionId::new(0xC400000D);
+pub const PSCI_SYSTEM_SUSPEND_32: FunctionId = FunctionId::new(0x8400000E);
+pub const PSCI_SYSTEM_SUSPEND_64: FunctionId = FunctionId::new(0xC400000E);
+pub const PSCI_SET_SUSPEND_MODE: FunctionId = FunctionId::new(0x8400000F);
+pub const PSCI_STAT_RESIDENCY_32: FunctionId = FunctionId::new(0x84000010);
+pub const PSCI_STAT_RESIDENCY_64: FunctionId = FunctionId::new(0xC4000010);
+pub const PSCI_STAT_COUNT_32: FunctionId = FunctionId::new(0x84000011);
+pub const PSCI_STAT_COUNT_64: FunctionId = FunctionId::new(0xC4000011);
 
 /// A version of PSCI.
 #[derive(Copy, Clone, Eq, Ord, PartialEq, PartialOrd)]
@@ -115,12 +130,32 @@ pub enum LowestAffinityLevel {
     Aff0Aff1Aff2Ignored = 3,
 }
 
+impl From<LowestAffinityLevel> for u32 {
+    fn from(lowest_affinity_level: LowestAffinityLevel) -> u32 {
+        lowest_affinity_level as u32
+    }
+}
+
 impl From<LowestAffinityLevel> for u64 {
     fn from(lowest_affinity_level: LowestAffinityLevel) -> u64 {
         (lowest_affinity_level as u32).into()
     }
 }
 
+impl TryFrom<u64> for LowestAffinityLevel {
+    type Error = Error;
+
+    fn try_from(value: u64) -> Result<Self, Error> {
+        match value {
+            0 => Ok(Self::All),
+            1 => Ok(Self::Aff0Ignored),
+            2 => Ok(Self::Aff0Aff1Ignored),
+            3 => Ok(Self::Aff0Aff1Aff2Ignored),
+            _ => Err(Error::InvalidParameters),
+        }
+    }
+}
+
 /// Affinity state values returned by `AFFINITY_INFO`.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum AffinityState {
@@ -145,6 +180,16 @@ impl TryFrom<i32> for AffinityState {
     }
 }
 
+impl TryFrom<i64> for AffinityState {
+    type Error = Error;
+
+    fn try_from(value: i64) -> Result<Self, Error> {
+        i32::try_from(value)
+            .map_err(|_| Error::from(value))?
+            .try_into()
+    }
+}
+
 /// The level of multicore support in the Trusted OS, as returned by `MIGRATE_INFO_TYPE`.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum MigrateType {
@@ -193,6 +238,46 @@ impl TryFrom<i32> for PowerState {
     }
 }
 
+impl TryFrom<i64> for PowerState {
+    type Error = Error;
+
+    fn try_from(value: i64) -> Result<Self, Error> {
+        i32::try_from(value)
+            .map_err(|_| Error::from(value))?
+            .try_into()
+    }
+}
+
+/// The type of power off requested by `SYSTEM_OFF2`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OffType {
+    /// Powers off the system, such that a subsequent boot may resume from a hibernation image.
+    HibernateOff = 1,
+}
+
+impl From<OffType> for u32 {
+    fn from(off_type: OffType) -> u32 {
+        off_type as u32
+    }
+}
+
+impl From<OffType> for u64 {
+    fn from(off_type: OffType) -> u64 {
+        (off_type as u32).into()
+    }
+}
+
+impl TryFrom<u64> for OffType {
+    type Error = Error;
+
+    fn try_from(value: u64) -> Result<Self, Error> {
+        match value {
+            1 => Ok(Self::HibernateOff),
+            _ => Err(Error::InvalidParameters),
+        }
+    }
+}
+
 /// The mode to be used by `CPU_SUSPEND`, as set by `PSCI_SET_SUSPEND_MODE`.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum SuspendMode {
@@ -207,3 +292,15 @@ impl From<SuspendMode> for u32 {
         suspend_mode as u32
     }
 }
+
+impl TryFrom<u64> for SuspendMode {
+    type Error = Error;
+
+    fn try_from(value: u64) -> Result<Self, Error> {
+        match value {
+            0 => Ok(Self::PlatformCoordinated),
+            1 => Ok(Self::OsInitiated),
+            _ => Err(Error::InvalidParameters),
+        }
+    }
+}
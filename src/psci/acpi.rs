@@ -0,0 +1,134 @@
+// Copyright 2026 the authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Discovery of the PSCI conduit from the ACPI Fixed ACPI Description Table (FADT).
+//!
+//! On ACPI systems the `ARM_BOOT_ARCH` field of the FADT, added in ACPI 5.1, says whether PSCI is
+//! implemented and whether to call it with HVC rather than SMC.
+
+use crate::Conduit;
+
+const SIGNATURE: &[u8; 4] = b"FACP";
+const LENGTH_OFFSET: usize = 4;
+const REVISION_OFFSET: usize = 8;
+const ARM_BOOT_ARCH_OFFSET: usize = 129;
+const MINOR_VERSION_OFFSET: usize = 131;
+/// The minimum length of a FADT which includes the `ARM_BOOT_ARCH` and minor version fields.
+const MIN_LENGTH: usize = 132;
+
+const PSCI_COMPLIANT: u16 = 1 << 0;
+const PSCI_USE_HVC: u16 = 1 << 1;
+
+/// Errors reading the PSCI conduit from the FADT.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum Error {
+    /// The table didn't have the `FACP` signature.
+    #[error("Table is not a FADT")]
+    InvalidSignature,
+    /// The length in the table header was too short to include `ARM_BOOT_ARCH`, or longer than the
+    /// buffer containing the table.
+    #[error("Invalid FADT length {0}")]
+    InvalidLength(usize),
+    /// The bytes of the table didn't sum to zero.
+    #[error("Invalid FADT checksum")]
+    InvalidChecksum,
+    /// The FADT is from a version of ACPI before 5.1, so doesn't have the `ARM_BOOT_ARCH` field.
+    #[error("FADT revision {major}.{minor} doesn't support ARM_BOOT_ARCH")]
+    UnsupportedRevision {
+        /// The major revision from the table header.
+        major: u8,
+        /// The FADT minor version.
+        minor: u8,
+    },
+}
+
+/// Returns the conduit to use for PSCI calls according to the given raw FADT.
+///
+/// Returns [`Conduit::None`] if the FADT says that PSCI is not implemented.
+pub fn conduit(fadt: &[u8]) -> Result<Conduit, Error> {
+    if fadt.get(..SIGNATURE.len()) != Some(SIGNATURE) {
+        return Err(Error::InvalidSignature);
+    }
+    let length = fadt
+        .get(LENGTH_OFFSET..LENGTH_OFFSET + 4)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()) as usize)
+        .ok_or(Error::InvalidLength(fadt.len()))?;
+    if length < MIN_LENGTH || length > fadt.len() {
+        return Err(Error::InvalidLength(length));
+    }
+    let fadt = &fadt[..length];
+    if fadt.iter().fold(0u8, |sum, &b| sum.wrapping_add(b)) != 0 {
+        return Err(Error::InvalidChecksum);
+    }
+    let major = fadt[REVISION_OFFSET];
+    let minor = fadt[MINOR_VERSION_OFFSET];
+    if (major, minor) < (5, 1) {
+        return Err(Error::UnsupportedRevision { major, minor });
+    }
+
+    let arm_boot_arch =
+        u16::from_le_bytes([fadt[ARM_BOOT_ARCH_OFFSET], fadt[ARM_BOOT_ARCH_OFFSET + 1]]);
+    Ok(if arm_boot_arch & PSCI_COMPLIANT == 0 {
+        Conduit::None
+    } else if arm_boot_arch & PSCI_USE_HVC != 0 {
+        Conduit::Hvc
+    } else {
+        Conduit::Smc
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds an ACPI 6.5 FADT with the given `ARM_BOOT_ARCH` flags and a valid checksum.
+    fn build_fadt(arm_boot_arch: u16) -> [u8; 276] {
+        let mut fadt = [0; 276];
+        fadt[..4].copy_from_slice(SIGNATURE);
+        fadt[LENGTH_OFFSET..LENGTH_OFFSET + 4].copy_from_slice(&276u32.to_le_bytes());
+        fadt[REVISION_OFFSET] = 6;
+        fadt[MINOR_VERSION_OFFSET] = 5;
+        fadt[ARM_BOOT_ARCH_OFFSET..ARM_BOOT_ARCH_OFFSET + 2]
+            .copy_from_slice(&arm_boot_arch.to_le_bytes());
+        fix_checksum(&mut fadt);
+        fadt
+    }
+
+    fn fix_checksum(fadt: &mut [u8]) {
+        fadt[9] = 0;
+        let sum = fadt.iter().fold(0u8, |sum, &b| sum.wrapping_add(b));
+        fadt[9] = 0u8.wrapping_sub(sum);
+    }
+
+    #[test]
+    fn conduit_from_flags() {
+        assert_eq!(conduit(&build_fadt(0)), Ok(Conduit::None));
+        assert_eq!(conduit(&build_fadt(PSCI_COMPLIANT)), Ok(Conduit::Smc));
+        assert_eq!(
+            conduit(&build_fadt(PSCI_COMPLIANT | PSCI_USE_HVC)),
+            Ok(Conduit::Hvc)
+        );
+    }
+
+    #[test]
+    fn invalid_tables() {
+        let mut fadt = build_fadt(PSCI_COMPLIANT);
+        assert_eq!(conduit(&fadt[..200]), Err(Error::InvalidLength(276)));
+        fadt[0] = b'X';
+        assert_eq!(conduit(&fadt), Err(Error::InvalidSignature));
+
+        let mut fadt = build_fadt(PSCI_COMPLIANT);
+        fadt[ARM_BOOT_ARCH_OFFSET] ^= PSCI_USE_HVC as u8;
+        assert_eq!(conduit(&fadt), Err(Error::InvalidChecksum));
+
+        let mut fadt = build_fadt(PSCI_COMPLIANT);
+        fadt[REVISION_OFFSET] = 5;
+        fadt[MINOR_VERSION_OFFSET] = 0;
+        fix_checksum(&mut fadt);
+        assert_eq!(
+            conduit(&fadt),
+            Err(Error::UnsupportedRevision { major: 5, minor: 0 })
+        );
+    }
+}
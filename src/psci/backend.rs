@@ -0,0 +1,494 @@
+// Copyright 2022 the authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Functions to make PSCI calls via a [`Backend`] instance, which may carry state.
+//!
+//! These are the same as the functions in [`psci`](super), but take the conduit as a reference
+//! rather than a type parameter, e.g. `psci::backend::cpu_off(&conduit)` rather than
+//! `psci::cpu_off::<Smc>()`.
+//!
+//! PSCI predates SMCCC 1.1, so these use the short call variants which assume only the SMCCC 1.0
+//! register preservation rules.
+
+use super::{
+    AffinityState, CpuSuspendFeatures, Features, LowestAffinityLevel, MigrateType, OffType,
+    PSCI_AFFINITY_INFO_32, PSCI_AFFINITY_INFO_64, PSCI_CPU_DEFAULT_SUSPEND_32,
+    PSCI_CPU_DEFAULT_SUSPEND_64, PSCI_CPU_FREEZE, PSCI_CPU_OFF, PSCI_CPU_ON_32, PSCI_CPU_ON_64,
+    PSCI_CPU_SUSPEND_32, PSCI_CPU_SUSPEND_64, PSCI_FEATURES, PSCI_MEM_PROTECT,
+    PSCI_MEM_PROTECT_CHECK_RANGE_32, PSCI_MEM_PROTECT_CHECK_RANGE_64, PSCI_MIGRATE_32,
+    PSCI_MIGRATE_64, PSCI_MIGRATE_INFO_TYPE, PSCI_MIGRATE_INFO_UP_CPU_32,
+    PSCI_MIGRATE_INFO_UP_CPU_64, PSCI_NODE_HW_STATE_32, PSCI_NODE_HW_STATE_64,
+    PSCI_SET_SUSPEND_MODE, PSCI_STAT_COUNT_32, PSCI_STAT_COUNT_64, PSCI_STAT_RESIDENCY_32,
+    PSCI_STAT_RESIDENCY_64, PSCI_SYSTEM_OFF, PSCI_SYSTEM_OFF2_32, PSCI_SYSTEM_OFF2_64,
+    PSCI_SYSTEM_RESET, PSCI_SYSTEM_RESET2_32, PSCI_SYSTEM_RESET2_64, PSCI_SYSTEM_SUSPEND_32,
+    PSCI_SYSTEM_SUSPEND_64, PSCI_VERSION, PowerState, PowerStateFormat, SuspendMode, Version,
+    error::Error,
+};
+use crate::{Backend, FunctionId, define_calls};
+
+define_calls! {
+    /// Returns the version of PSCI implemented.
+    pub fn version() -> Result<Version, Error> = call32_short(PSCI_VERSION) => try_from;
+
+    /// Suspends execution of a core or topology node.
+    ///
+    /// The `power_state` may be built with [`PowerStateParam`](super::PowerStateParam).
+    pub fn cpu_suspend(
+        power_state: u32,
+        entry_point_address: u64,
+        context_id: u64,
+    ) -> Result<(), Error> = call64_short(PSCI_CPU_SUSPEND_64) => success;
+
+    /// Suspends execution of a core or topology node.
+    ///
+    /// The `power_state` may be built with [`PowerStateParam`](super::PowerStateParam).
+    pub fn cpu_suspend_32(
+        power_state: u32,
+        entry_point_address: u32,
+        context_id: u32,
+    ) -> Result<(), Error> = call32_short(PSCI_CPU_SUSPEND_32) => success;
+
+    /// Powers down the current core.
+    pub fn cpu_off() -> Result<(), Error> = call32_short(PSCI_CPU_OFF) => success;
+
+    /// Powers up a core.
+    pub fn cpu_on(
+        target_cpu: u64,
+        entry_point_address: u64,
+        context_id: u64,
+    ) -> Result<(), Error> = call64_short(PSCI_CPU_ON_64) => success;
+
+    /// Powers up a core.
+    pub fn cpu_on_32(
+        target_cpu: u32,
+        entry_point_address: u32,
+        context_id: u32,
+    ) -> Result<(), Error> = call32_short(PSCI_CPU_ON_32) => success;
+
+    /// Gets the status of an affinity instance.
+    pub fn affinity_info(
+        target_affinity: u64,
+        lowest_affinity_level: LowestAffinityLevel,
+    ) -> Result<AffinityState, Error> = call64_short(PSCI_AFFINITY_INFO_64) => try_from;
+
+    /// Gets the status of an affinity instance.
+    pub fn affinity_info_32(
+        target_affinity: u32,
+        lowest_affinity_level: LowestAffinityLevel,
+    ) -> Result<AffinityState, Error> = call32_short(PSCI_AFFINITY_INFO_32) => try_from;
+
+    /// Asks the Trusted OS to migrate its context to a specific core.
+    pub fn migrate(target_cpu: u64) -> Result<(), Error> =
+        call64_short(PSCI_MIGRATE_64) => success;
+
+    /// Asks the Trusted OS to migrate its context to a specific core.
+    pub fn migrate_32(target_cpu: u32) -> Result<(), Error> =
+        call32_short(PSCI_MIGRATE_32) => success;
+
+    /// Identifies the levelof multicore support in the Trusted OS.
+    pub fn migrate_info_type() -> Result<MigrateType, Error> =
+        call32_short(PSCI_MIGRATE_INFO_TYPE) => try_from;
+
+    /// Returns the MPIDR value of the current resident core of the Trusted OS.
+    pub fn migrate_info_up_cpu() -> u64 = call64_short(PSCI_MIGRATE_INFO_UP_CPU_64) => raw;
+
+    /// Returns the MPIDR value of the current resident core of the Trusted OS.
+    pub fn migrate_info_up_cpu_32() -> u32 = call32_short(PSCI_MIGRATE_INFO_UP_CPU_32) => raw;
+
+    /// Shuts down the system.
+    pub fn system_off() -> Result<(), Error> = call32_short(PSCI_SYSTEM_OFF) => success;
+
+    /// Shuts down the system in the given way, e.g. to hibernate.
+    pub fn system_off2(off_type: OffType, cookie: u64) -> Result<(), Error> =
+        call64_short(PSCI_SYSTEM_OFF2_64) => success;
+
+    /// Shuts down the system in the given way, e.g. to hibernate.
+    pub fn system_off2_32(off_type: OffType, cookie: u32) -> Result<(), Error> =
+        call32_short(PSCI_SYSTEM_OFF2_32) => success;
+
+    /// Resets the system.
+    pub fn system_reset() -> Result<(), Error> = call32_short(PSCI_SYSTEM_RESET) => success;
+
+    /// Resets the system in an architectural or vendor-specific way.
+    pub fn system_reset2(reset_type: u32, cookie: u64) -> Result<(), Error> =
+        call64_short(PSCI_SYSTEM_RESET2_64) => success;
+
+    /// Resets the system in an architectural or vendor-specific way.
+    pub fn system_reset2_32(reset_type: u32, cookie: u32) -> Result<(), Error> =
+        call32_short(PSCI_SYSTEM_RESET2_32) => success;
+
+    /// Checks whether a memory range is protected by `MEM_PROTECT`.
+    pub fn mem_protect_check_range(base: u64, length: u64) -> Result<(), Error> =
+        call64_short(PSCI_MEM_PROTECT_CHECK_RANGE_64) => success;
+
+    /// Checks whether a memory range is protected by `MEM_PROTECT`.
+    pub fn mem_protect_check_range_32(base: u32, length: u32) -> Result<(), Error> =
+        call32_short(PSCI_MEM_PROTECT_CHECK_RANGE_32) => success;
+
+    /// Queries whether `SMCCC_VERSION` or a specific PSCI function is implemented, and what
+    /// features are supported.
+    pub fn psci_features(psci_function_id: FunctionId) -> Result<u32, Error> =
+        call32_short(PSCI_FEATURES) => positive;
+
+    /// Puts the current core into an implementation-defined low power state.
+    pub fn cpu_freeze() -> Result<(), Error> = call32_short(PSCI_CPU_FREEZE) => success;
+
+    /// Puts the current core into an implementation-defined low power state.
+    pub fn cpu_default_suspend(entry_point_address: u64, context_id: u64) -> Result<(), Error> =
+        call64_short(PSCI_CPU_DEFAULT_SUSPEND_64) => success;
+
+    /// Puts the current core into an implementation-defined low power state.
+    pub fn cpu_default_suspend_32(entry_point_address: u32, context_id: u32) -> Result<(), Error> =
+        call32_short(PSCI_CPU_DEFAULT_SUSPEND_32) => success;
+
+    /// Retuns the true hardware state of a node in the power domain topology.
+    pub fn node_hw_state(target_cpu: u64, power_level: u32) -> Result<PowerState, Error> =
+        call64_short(PSCI_NODE_HW_STATE_64) => try_from;
+
+    /// Retuns the true hardware state of a node in the power domain topology.
+    pub fn node_hw_state_32(target_cpu: u32, power_level: u32) -> Result<PowerState, Error> =
+        call32_short(PSCI_NODE_HW_STATE_32) => try_from;
+
+    /// Suspends the system to RAM.
+    pub fn system_suspend(entry_point_address: u64, context_id: u64) -> Result<(), Error> =
+        call64_short(PSCI_SYSTEM_SUSPEND_64) => success;
+
+    /// Suspends the system to RAM.
+    pub fn system_suspend_32(entry_point_address: u32, context_id: u32) -> Result<(), Error> =
+        call32_short(PSCI_SYSTEM_SUSPEND_32) => success;
+
+    /// Sets the mode used by `CPU_SUSPEND`.
+    pub fn set_suspend_mode(mode: SuspendMode) -> Result<(), Error> =
+        call32_short(PSCI_SET_SUSPEND_MODE) => success;
+
+    /// Returns the amount of time in microseconds that the platform has spent in the given power
+    /// state since cold boot.
+    pub fn stat_residency(target_cpu: u64, power_state: u32) -> u64 =
+        call64_short(PSCI_STAT_RESIDENCY_64) => raw;
+
+    /// Returns the amount of time in microseconds that the platform has spent in the given power
+    /// state since cold boot.
+    pub fn stat_residency_32(target_cpu: u32, power_state: u32) -> u32 =
+        call32_short(PSCI_STAT_RESIDENCY_32) => raw;
+
+    /// Returns the number of times the platform has used the given power state since cold boot.
+    pub fn stat_count(target_cpu: u64, power_state: u32) -> u64 =
+        call64_short(PSCI_STAT_COUNT_64) => raw;
+
+    /// Returns the number of times the platform has used the given power state since cold boot.
+    pub fn stat_count_32(target_cpu: u32, power_state: u32) -> u32 =
+        call32_short(PSCI_STAT_COUNT_32) => raw;
+}
+
+/// Enables or disables memory protection.
+pub fn mem_protect<C: Backend + ?Sized>(conduit: &C, enable: bool) -> Result<bool, Error> {
+    match conduit.call32_short(PSCI_MEM_PROTECT, [enable as u32, 0, 0])[0] as i32 {
+        0 => Ok(false),
+        1 => Ok(true),
+        error => Err(error.into()),
+    }
+}
+
+/// Returns the format of the `power_state` parameter which the firmware uses for `CPU_SUSPEND`,
+/// `STAT_RESIDENCY` and `STAT_COUNT`, as reported by `PSCI_FEATURES`.
+///
+/// This queries the features of `CPU_SUSPEND_64`, or `CPU_SUSPEND_32` if the firmware doesn't
+/// implement the SMC64 version.
+pub fn power_state_format<C: Backend + ?Sized>(conduit: &C) -> Result<PowerStateFormat, Error> {
+    let features = match psci_features(conduit, PSCI_CPU_SUSPEND_64) {
+        Err(Error::NotSupported) => psci_features(conduit, PSCI_CPU_SUSPEND_32)?,
+        features => features?,
+    };
+    Ok(CpuSuspendFeatures::from(features).power_state_format)
+}
+
+/// Queries whether a specific PSCI function is implemented, and decodes the features which it
+/// supports.
+pub fn function_features<C: Backend + ?Sized>(
+    conduit: &C,
+    psci_function_id: FunctionId,
+) -> Result<Features, Error> {
+    Ok(Features::decode(
+        psci_function_id,
+        psci_features(conduit, psci_function_id)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::Mock;
+
+    #[test]
+    fn version_decodes() {
+        Mock::expect_call32(PSCI_VERSION, &[], &[0x0001_0001]);
+        assert_eq!(version(&Mock), Ok(Version { major: 1, minor: 1 }));
+        Mock::expect_call32(PSCI_VERSION, &[], &[-1i32 as u32]);
+        assert_eq!(version(&Mock), Err(Error::NotSupported));
+        Mock::finish();
+    }
+
+    #[test]
+    fn cpu_suspend_packs_args() {
+        Mock::expect_call64(PSCI_CPU_SUSPEND_64, &[0x1_0000, 0x8000_1000, 42], &[0]);
+        assert_eq!(cpu_suspend(&Mock, 0x1_0000, 0x8000_1000, 42), Ok(()));
+        Mock::expect_call32(
+            PSCI_CPU_SUSPEND_32,
+            &[0x1_0000, 0x8000_1000, 42],
+            &[-3i32 as u32],
+        );
+        assert_eq!(
+            cpu_suspend_32(&Mock, 0x1_0000, 0x8000_1000, 42),
+            Err(Error::Denied)
+        );
+        Mock::finish();
+    }
+
+    #[test]
+    fn cpu_off_decodes() {
+        Mock::expect_call32(PSCI_CPU_OFF, &[], &[-3i32 as u32]);
+        assert_eq!(cpu_off(&Mock), Err(Error::Denied));
+        Mock::finish();
+    }
+
+    #[test]
+    fn cpu_on_packs_args() {
+        Mock::expect_call64(PSCI_CPU_ON_64, &[0x1_0000_0101, 0x8000_0000, 7], &[0]);
+        assert_eq!(cpu_on(&Mock, 0x1_0000_0101, 0x8000_0000, 7), Ok(()));
+        Mock::expect_call64(PSCI_CPU_ON_64, &[1, 0x8000_0000, 7], &[-4i64 as u64]);
+        assert_eq!(cpu_on(&Mock, 1, 0x8000_0000, 7), Err(Error::AlreadyOn));
+        Mock::expect_call32(PSCI_CPU_ON_32, &[1, 0x8000_0000, 7], &[-9i32 as u32]);
+        assert_eq!(
+            cpu_on_32(&Mock, 1, 0x8000_0000, 7),
+            Err(Error::InvalidAddress)
+        );
+        Mock::finish();
+    }
+
+    #[test]
+    fn affinity_info_decodes() {
+        Mock::expect_call64(PSCI_AFFINITY_INFO_64, &[0x101, 1], &[2]);
+        assert_eq!(
+            affinity_info(&Mock, 0x101, LowestAffinityLevel::Aff0Ignored),
+            Ok(AffinityState::OnPending)
+        );
+        // 64-bit results aren't truncated before being decoded.
+        Mock::expect_call64(PSCI_AFFINITY_INFO_64, &[0x101, 0], &[0x1_0000_0000]);
+        assert_eq!(
+            affinity_info(&Mock, 0x101, LowestAffinityLevel::All),
+            Err(Error::Unknown(0x1_0000_0000))
+        );
+        Mock::expect_call64(PSCI_AFFINITY_INFO_64, &[0x101, 0], &[-2i64 as u64]);
+        assert_eq!(
+            affinity_info(&Mock, 0x101, LowestAffinityLevel::All),
+            Err(Error::InvalidParameters)
+        );
+        Mock::expect_call32(PSCI_AFFINITY_INFO_32, &[0x101, 3], &[1]);
+        assert_eq!(
+            affinity_info_32(&Mock, 0x101, LowestAffinityLevel::Aff0Aff1Aff2Ignored),
+            Ok(AffinityState::Off)
+        );
+        Mock::expect_call32(PSCI_AFFINITY_INFO_32, &[0x101, 0], &[-2i32 as u32]);
+        assert_eq!(
+            affinity_info_32(&Mock, 0x101, LowestAffinityLevel::All),
+            Err(Error::InvalidParameters)
+        );
+        Mock::finish();
+    }
+
+    #[test]
+    fn migrate_packs_args() {
+        Mock::expect_call64(PSCI_MIGRATE_64, &[0x1_0000_0000], &[0]);
+        assert_eq!(migrate(&Mock, 0x1_0000_0000), Ok(()));
+        Mock::expect_call32(PSCI_MIGRATE_32, &[3], &[-7i32 as u32]);
+        assert_eq!(migrate_32(&Mock, 3), Err(Error::NotPresent));
+        Mock::finish();
+    }
+
+    #[test]
+    fn migrate_info_decodes() {
+        Mock::expect_call32(PSCI_MIGRATE_INFO_TYPE, &[], &[2]);
+        assert_eq!(
+            migrate_info_type(&Mock),
+            Ok(MigrateType::MigrationNotRequired)
+        );
+        Mock::expect_call64(PSCI_MIGRATE_INFO_UP_CPU_64, &[], &[0x1_0000_0002]);
+        assert_eq!(migrate_info_up_cpu(&Mock), 0x1_0000_0002);
+        Mock::expect_call32(PSCI_MIGRATE_INFO_UP_CPU_32, &[], &[2]);
+        assert_eq!(migrate_info_up_cpu_32(&Mock), 2);
+        Mock::finish();
+    }
+
+    #[test]
+    fn system_off_and_reset() {
+        Mock::expect_call32(PSCI_SYSTEM_OFF, &[], &[-6i32 as u32]);
+        assert_eq!(system_off(&Mock), Err(Error::InternalFailure));
+        Mock::expect_call64(PSCI_SYSTEM_OFF2_64, &[1, 0x1_0000_0000], &[-3i64 as u64]);
+        assert_eq!(
+            system_off2(&Mock, OffType::HibernateOff, 0x1_0000_0000),
+            Err(Error::Denied)
+        );
+        Mock::expect_call32(PSCI_SYSTEM_OFF2_32, &[1, 0], &[-2i32 as u32]);
+        assert_eq!(
+            system_off2_32(&Mock, OffType::HibernateOff, 0),
+            Err(Error::InvalidParameters)
+        );
+        Mock::expect_call32(PSCI_SYSTEM_RESET, &[], &[-1i32 as u32]);
+        assert_eq!(system_reset(&Mock), Err(Error::NotSupported));
+        Mock::expect_call64(PSCI_SYSTEM_RESET2_64, &[0x8000_0000, 0x1_0000_0000], &[0]);
+        assert_eq!(system_reset2(&Mock, 0x8000_0000, 0x1_0000_0000), Ok(()));
+        Mock::expect_call32(PSCI_SYSTEM_RESET2_32, &[0x8000_0000, 5], &[-2i32 as u32]);
+        assert_eq!(
+            system_reset2_32(&Mock, 0x8000_0000, 5),
+            Err(Error::InvalidParameters)
+        );
+        Mock::finish();
+    }
+
+    #[test]
+    fn mem_protect_decodes() {
+        Mock::expect_call32(PSCI_MEM_PROTECT, &[1], &[0]);
+        assert_eq!(mem_protect(&Mock, true), Ok(false));
+        Mock::expect_call32(PSCI_MEM_PROTECT, &[0], &[1]);
+        assert_eq!(mem_protect(&Mock, false), Ok(true));
+        Mock::expect_call64(
+            PSCI_MEM_PROTECT_CHECK_RANGE_64,
+            &[0x1_0000_0000, 0x1000],
+            &[-3i64 as u64],
+        );
+        assert_eq!(
+            mem_protect_check_range(&Mock, 0x1_0000_0000, 0x1000),
+            Err(Error::Denied)
+        );
+        Mock::expect_call32(PSCI_MEM_PROTECT_CHECK_RANGE_32, &[0x1000, 0x1000], &[0]);
+        assert_eq!(mem_protect_check_range_32(&Mock, 0x1000, 0x1000), Ok(()));
+        Mock::finish();
+    }
+
+    #[test]
+    fn psci_features_decodes() {
+        Mock::expect_call32(PSCI_FEATURES, &[PSCI_CPU_SUSPEND_64.into()], &[0b11]);
+        assert_eq!(psci_features(&Mock, PSCI_CPU_SUSPEND_64), Ok(0b11));
+        Mock::expect_call32(PSCI_FEATURES, &[PSCI_CPU_FREEZE.into()], &[-1i32 as u32]);
+        assert_eq!(
+            psci_features(&Mock, PSCI_CPU_FREEZE),
+            Err(Error::NotSupported)
+        );
+        Mock::finish();
+    }
+
+    #[test]
+    fn function_features_decodes() {
+        Mock::expect_call32(PSCI_FEATURES, &[PSCI_CPU_SUSPEND_32.into()], &[0b01]);
+        assert_eq!(
+            function_features(&Mock, PSCI_CPU_SUSPEND_32),
+            Ok(Features::CpuSuspend(CpuSuspendFeatures {
+                os_initiated_mode: true,
+                power_state_format: PowerStateFormat::Original,
+            }))
+        );
+        Mock::expect_call32(PSCI_FEATURES, &[PSCI_CPU_FREEZE.into()], &[0]);
+        assert_eq!(
+            function_features(&Mock, PSCI_CPU_FREEZE),
+            Ok(Features::Other(0))
+        );
+        Mock::finish();
+    }
+
+    #[test]
+    fn power_state_format_queries_features() {
+        Mock::expect_call32(PSCI_FEATURES, &[PSCI_CPU_SUSPEND_64.into()], &[0b10]);
+        assert_eq!(power_state_format(&Mock), Ok(PowerStateFormat::Extended));
+        Mock::expect_call32(
+            PSCI_FEATURES,
+            &[PSCI_CPU_SUSPEND_64.into()],
+            &[-1i32 as u32],
+        );
+        Mock::expect_call32(PSCI_FEATURES, &[PSCI_CPU_SUSPEND_32.into()], &[0]);
+        assert_eq!(power_state_format(&Mock), Ok(PowerStateFormat::Original));
+        Mock::finish();
+    }
+
+    #[test]
+    fn cpu_freeze_and_default_suspend() {
+        Mock::expect_call32(PSCI_CPU_FREEZE, &[], &[0]);
+        assert_eq!(cpu_freeze(&Mock), Ok(()));
+        Mock::expect_call64(
+            PSCI_CPU_DEFAULT_SUSPEND_64,
+            &[0x8000_0000, 0x1_0000_0000],
+            &[0],
+        );
+        assert_eq!(
+            cpu_default_suspend(&Mock, 0x8000_0000, 0x1_0000_0000),
+            Ok(())
+        );
+        Mock::expect_call32(
+            PSCI_CPU_DEFAULT_SUSPEND_32,
+            &[0x8000_0000, 1],
+            &[-9i32 as u32],
+        );
+        assert_eq!(
+            cpu_default_suspend_32(&Mock, 0x8000_0000, 1),
+            Err(Error::InvalidAddress)
+        );
+        Mock::finish();
+    }
+
+    #[test]
+    fn node_hw_state_decodes() {
+        Mock::expect_call64(PSCI_NODE_HW_STATE_64, &[0x100, 1], &[1]);
+        assert_eq!(node_hw_state(&Mock, 0x100, 1), Ok(PowerState::HwOff));
+        Mock::expect_call32(PSCI_NODE_HW_STATE_32, &[0x100, 0], &[2]);
+        assert_eq!(node_hw_state_32(&Mock, 0x100, 0), Ok(PowerState::HwStandby));
+        Mock::expect_call32(PSCI_NODE_HW_STATE_32, &[0x100, 9], &[-2i32 as u32]);
+        assert_eq!(
+            node_hw_state_32(&Mock, 0x100, 9),
+            Err(Error::InvalidParameters)
+        );
+        Mock::finish();
+    }
+
+    #[test]
+    fn system_suspend_packs_args() {
+        Mock::expect_call64(PSCI_SYSTEM_SUSPEND_64, &[0x8000_0000, 0x1_0000_0000], &[0]);
+        assert_eq!(system_suspend(&Mock, 0x8000_0000, 0x1_0000_0000), Ok(()));
+        Mock::expect_call32(PSCI_SYSTEM_SUSPEND_32, &[0x8000_0000, 2], &[-3i32 as u32]);
+        assert_eq!(system_suspend_32(&Mock, 0x8000_0000, 2), Err(Error::Denied));
+        Mock::finish();
+    }
+
+    #[test]
+    fn set_suspend_mode_packs_args() {
+        Mock::expect_call32(PSCI_SET_SUSPEND_MODE, &[1], &[0]);
+        assert_eq!(set_suspend_mode(&Mock, SuspendMode::OsInitiated), Ok(()));
+        Mock::finish();
+    }
+
+    #[test]
+    fn stats_return_raw_values() {
+        Mock::expect_call64(PSCI_STAT_RESIDENCY_64, &[0x100, 0x1_0000], &[0x1_0000_0000]);
+        assert_eq!(stat_residency(&Mock, 0x100, 0x1_0000), 0x1_0000_0000);
+        Mock::expect_call32(PSCI_STAT_RESIDENCY_32, &[0x100, 0x1_0000], &[1234]);
+        assert_eq!(stat_residency_32(&Mock, 0x100, 0x1_0000), 1234);
+        Mock::expect_call64(PSCI_STAT_COUNT_64, &[0x100, 0x1_0000], &[5]);
+        assert_eq!(stat_count(&Mock, 0x100, 0x1_0000), 5);
+        Mock::expect_call32(PSCI_STAT_COUNT_32, &[0x100, 0x1_0000], &[6]);
+        assert_eq!(stat_count_32(&Mock, 0x100, 0x1_0000), 6);
+        Mock::finish();
+    }
+
+    #[test]
+    #[should_panic(expected = "Unexpected call32")]
+    fn unexpected_call_panics() {
+        Mock::expect_call32(PSCI_CPU_OFF, &[], &[0]);
+        let _ = system_off(&Mock);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected calls were never made")]
+    fn unconsumed_expectation_panics() {
+        Mock::expect_call32(PSCI_CPU_OFF, &[], &[0]);
+        Mock::finish();
+    }
+}
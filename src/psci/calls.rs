@@ -2,468 +2,181 @@
 // This project is dual-licensed under Apache 2.0 and MIT terms.
 // See LICENSE-APACHE and LICENSE-MIT for details.
 
-//! Functions to make PSCI calls.
+//! Functions to make PSCI calls via a [`Call`](crate::Call) type.
 
 use super::{
-    AffinityState, LowestAffinityLevel, MigrateType, PSCI_AFFINITY_INFO_32, PSCI_AFFINITY_INFO_64,
-    PSCI_CPU_DEFAULT_SUSPEND_32, PSCI_CPU_DEFAULT_SUSPEND_64, PSCI_CPU_FREEZE, PSCI_CPU_OFF,
-    PSCI_CPU_ON_32, PSCI_CPU_ON_64, PSCI_CPU_SUSPEND_32, PSCI_CPU_SUSPEND_64, PSCI_FEATURES,
-    PSCI_MEM_PROTECT, PSCI_MEM_PROTECT_CHECK_RANGE_32, PSCI_MEM_PROTECT_CHECK_RANGE_64,
-    PSCI_MIGRATE_32, PSCI_MIGRATE_64, PSCI_MIGRATE_INFO_TYPE, PSCI_MIGRATE_INFO_UP_CPU_32,
-    PSCI_MIGRATE_INFO_UP_CPU_64, PSCI_NODE_HW_STATE_32, PSCI_NODE_HW_STATE_64,
-    PSCI_SET_SUSPEND_MODE, PSCI_STAT_COUNT_32, PSCI_STAT_COUNT_64, PSCI_STAT_RESIDENCY_32,
-    PSCI_STAT_RESIDENCY_64, PSCI_SYSTEM_OFF, PSCI_SYSTEM_RESET, PSCI_SYSTEM_RESET2_32,
-    PSCI_SYSTEM_RESET2_64, PSCI_SYSTEM_SUSPEND_32, PSCI_SYSTEM_SUSPEND_64, PSCI_VERSION,
-    PowerState, SuspendMode, Version, error::Error,
-};
-use crate::{
-    Call,
-    error::{positive_or_error_32, success_or_error_32, success_or_error_64},
+    AffinityState, Features, LowestAffinityLevel, MigrateType, OffType, PowerState,
+    PowerStateFormat, SuspendMode, Version, backend, error::Error,
 };
+use crate::{FunctionId, macros::define_static_calls};
 
-/// Returns the version of PSCI implemented.
-pub fn version<C: Call>() -> Result<Version, Error> {
-    (C::call32(PSCI_VERSION, [0; 7])[0] as i32).try_into()
-}
+define_static_calls! {
+    backend;
 
-/// Suspends execution of a core or topology node.
-pub fn cpu_suspend<C: Call>(
-    power_state: u32,
-    entry_point_address: u64,
-    context_id: u64,
-) -> Result<(), Error> {
-    success_or_error_64(
-        C::call64(
-            PSCI_CPU_SUSPEND_64,
-            [
-                power_state.into(),
-                entry_point_address,
-                context_id,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-            ],
-        )[0],
-    )
-}
+    /// Returns the version of PSCI implemented.
+    pub fn version<C: Call>() -> Result<Version, Error>;
 
-/// Suspends execution of a core or topology node.
-pub fn cpu_suspend_32<C: Call>(
-    power_state: u32,
-    entry_point_address: u32,
-    context_id: u32,
-) -> Result<(), Error> {
-    success_or_error_32(
-        C::call32(
-            PSCI_CPU_SUSPEND_32,
-            [power_state, entry_point_address, context_id, 0, 0, 0, 0],
-        )[0],
-    )
-}
+    /// Suspends execution of a core or topology node.
+    ///
+    /// The `power_state` may be built with [`PowerStateParam`](super::PowerStateParam).
+    pub fn cpu_suspend<C: Call64>(
+        power_state: u32,
+        entry_point_address: u64,
+        context_id: u64,
+    ) -> Result<(), Error>;
 
-/// Powers down the current core.
-pub fn cpu_off<C: Call>() -> Result<(), Error> {
-    success_or_error_32(C::call32(PSCI_CPU_OFF, [0; 7])[0])
-}
+    /// Suspends execution of a core or topology node.
+    ///
+    /// The `power_state` may be built with [`PowerStateParam`](super::PowerStateParam).
+    pub fn cpu_suspend_32<C: Call>(
+        power_state: u32,
+        entry_point_address: u32,
+        context_id: u32,
+    ) -> Result<(), Error>;
 
-/// Powers up a core.
-pub fn cpu_on<C: Call>(
-    target_cpu: u64,
-    entry_point_address: u64,
-    context_id: u64,
-) -> Result<(), Error> {
-    success_or_error_64(
-        C::call64(
-            PSCI_CPU_ON_64,
-            [
-                target_cpu,
-                entry_point_address,
-                context_id,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-            ],
-        )[0],
-    )
-}
+    /// Powers down the current core.
+    pub fn cpu_off<C: Call>() -> Result<(), Error>;
 
-/// Powers up a core.
-pub fn cpu_on_32<C: Call>(
-    target_cpu: u32,
-    entry_point_address: u32,
-    context_id: u32,
-) -> Result<(), Error> {
-    success_or_error_32(
-        C::call32(
-            PSCI_CPU_ON_32,
-            [target_cpu, entry_point_address, context_id, 0, 0, 0, 0],
-        )[0],
-    )
-}
+    /// Powers up a core.
+    pub fn cpu_on<C: Call64>(
+        target_cpu: u64,
+        entry_point_address: u64,
+        context_id: u64,
+    ) -> Result<(), Error>;
 
-/// Gets the status of an affinity instance.
-pub fn affinity_info<C: Call>(
-    target_affinity: u64,
-    lowest_affinity_level: LowestAffinityLevel,
-) -> Result<AffinityState, Error> {
-    (C::call64(
-        PSCI_AFFINITY_INFO_64,
-        [
-            target_affinity,
-            lowest_affinity_level as u64,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-        ],
-    )[0] as i32)
-        .try_into()
-}
+    /// Powers up a core.
+    pub fn cpu_on_32<C: Call>(
+        target_cpu: u32,
+        entry_point_address: u32,
+        context_id: u32,
+    ) -> Result<(), Error>;
 
-/// Gets the status of an affinity instance.
-pub fn affinity_info_32<C: Call>(
-    target_affinity: u32,
-    lowest_affinity_level: LowestAffinityLevel,
-) -> Result<AffinityState, Error> {
-    (C::call32(
-        PSCI_AFFINITY_INFO_32,
-        [target_affinity, lowest_affinity_level as u32, 0, 0, 0, 0, 0],
-    )[0] as i32)
-        .try_into()
-}
+    /// Gets the status of an affinity instance.
+    pub fn affinity_info<C: Call64>(
+        target_affinity: u64,
+        lowest_affinity_level: LowestAffinityLevel,
+    ) -> Result<AffinityState, Error>;
 
-/// Asks the Trusted OS to migrate its context to a specific core.
-pub fn migrate<C: Call>(target_cpu: u64) -> Result<(), Error> {
-    success_or_error_64(
-        C::call64(
-            PSCI_MIGRATE_64,
-            [target_cpu, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
-        )[0],
-    )
-}
+    /// Gets the status of an affinity instance.
+    pub fn affinity_info_32<C: Call>(
+        target_affinity: u32,
+        lowest_affinity_level: LowestAffinityLevel,
+    ) -> Result<AffinityState, Error>;
 
-/// Asks the Trusted OS to migrate its context to a specific core.
-pub fn migrate_32<C: Call>(target_cpu: u32) -> Result<(), Error> {
-    success_or_error_32(C::call32(PSCI_MIGRATE_32, [target_cpu, 0, 0, 0, 0, 0, 0])[0])
-}
+    /// Asks the Trusted OS to migrate its context to a specific core.
+    pub fn migrate<C: Call64>(target_cpu: u64) -> Result<(), Error>;
 
-/// Identifies the levelof multicore support in the Trusted OS.
-pub fn migrate_info_type<C: Call>() -> Result<MigrateType, Error> {
-    (C::call32(PSCI_MIGRATE_INFO_TYPE, [0; 7])[0] as i32).try_into()
-}
+    /// Asks the Trusted OS to migrate its context to a specific core.
+    pub fn migrate_32<C: Call>(target_cpu: u32) -> Result<(), Error>;
 
-/// Returns the MPIDR value of the current resident core of the Trusted OS.
-pub fn migrate_info_up_cpu<C: Call>() -> u64 {
-    C::call64(PSCI_MIGRATE_INFO_UP_CPU_64, [0; 17])[0]
-}
+    /// Identifies the levelof multicore support in the Trusted OS.
+    pub fn migrate_info_type<C: Call>() -> Result<MigrateType, Error>;
 
-/// Returns the MPIDR value of the current resident core of the Trusted OS.
-pub fn migrate_info_up_cpu_32<C: Call>() -> u32 {
-    C::call32(PSCI_MIGRATE_INFO_UP_CPU_32, [0; 7])[0]
-}
+    /// Returns the MPIDR value of the current resident core of the Trusted OS.
+    pub fn migrate_info_up_cpu<C: Call64>() -> u64;
 
-/// Shuts down the system.
-pub fn system_off<C: Call>() -> Result<(), Error> {
-    success_or_error_32(C::call32(PSCI_SYSTEM_OFF, [0; 7])[0])
-}
+    /// Returns the MPIDR value of the current resident core of the Trusted OS.
+    pub fn migrate_info_up_cpu_32<C: Call>() -> u32;
+
+    /// Shuts down the system.
+    pub fn system_off<C: Call>() -> Result<(), Error>;
+
+    /// Shuts down the system in the given way, e.g. to hibernate.
+    pub fn system_off2<C: Call64>(off_type: OffType, cookie: u64) -> Result<(), Error>;
+
+    /// Shuts down the system in the given way, e.g. to hibernate.
+    pub fn system_off2_32<C: Call>(off_type: OffType, cookie: u32) -> Result<(), Error>;
+
+    /// Resets the system.
+    pub fn system_reset<C: Call>() -> Result<(), Error>;
+
+    /// Resets the system in an architectural or vendor-specific way.
+    pub fn system_reset2<C: Call64>(reset_type: u32, cookie: u64) -> Result<(), Error>;
+
+    /// Resets the system in an architectural or vendor-specific way.
+    pub fn system_reset2_32<C: Call>(reset_type: u32, cookie: u32) -> Result<(), Error>;
+
+    /// Checks whether a memory range is protected by `MEM_PROTECT`.
+    pub fn mem_protect_check_range<C: Call64>(base: u64, length: u64) -> Result<(), Error>;
+
+    /// Checks whether a memory range is protected by `MEM_PROTECT`.
+    pub fn mem_protect_check_range_32<C: Call>(base: u32, length: u32) -> Result<(), Error>;
+
+    /// Queries whether `SMCCC_VERSION` or a specific PSCI function is implemented, and what
+    /// features are supported.
+    pub fn psci_features<C: Call>(psci_function_id: FunctionId) -> Result<u32, Error>;
+
+    /// Puts the current core into an implementation-defined low power state.
+    pub fn cpu_freeze<C: Call>() -> Result<(), Error>;
+
+    /// Puts the current core into an implementation-defined low power state.
+    pub fn cpu_default_suspend<C: Call64>(entry_point_address: u64, context_id: u64) -> Result<(), Error>;
+
+    /// Puts the current core into an implementation-defined low power state.
+    pub fn cpu_default_suspend_32<C: Call>(entry_point_address: u32, context_id: u32) -> Result<(), Error>;
+
+    /// Retuns the true hardware state of a node in the power domain topology.
+    pub fn node_hw_state<C: Call64>(target_cpu: u64, power_level: u32) -> Result<PowerState, Error>;
+
+    /// Retuns the true hardware state of a node in the power domain topology.
+    pub fn node_hw_state_32<C: Call>(target_cpu: u32, power_level: u32) -> Result<PowerState, Error>;
+
+    /// Suspends the system to RAM.
+    pub fn system_suspend<C: Call64>(entry_point_address: u64, context_id: u64) -> Result<(), Error>;
 
-/// Resets the system.
-pub fn system_reset<C: Call>() -> Result<(), Error> {
-    success_or_error_32(C::call32(PSCI_SYSTEM_RESET, [0; 7])[0])
-}
+    /// Suspends the system to RAM.
+    pub fn system_suspend_32<C: Call>(entry_point_address: u32, context_id: u32) -> Result<(), Error>;
+
+    /// Sets the mode used by `CPU_SUSPEND`.
+    pub fn set_suspend_mode<C: Call>(mode: SuspendMode) -> Result<(), Error>;
 
-/// Resets the system in an architectural or vendor-specific way.
-pub fn system_reset2<C: Call>(reset_type: u32, cookie: u64) -> Result<(), Error> {
-    success_or_error_64(
-        C::call64(
-            PSCI_SYSTEM_RESET2_64,
-            [
-                reset_type.into(),
-                cookie,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-            ],
-        )[0],
-    )
-}
+    /// Returns the amount of time in microseconds that the platform has spent in the given power
+    /// state since cold boot.
+    pub fn stat_residency<C: Call64>(target_cpu: u64, power_state: u32) -> u64;
 
-/// Resets the system in an architectural or vendor-specific way.
-pub fn system_reset2_32<C: Call>(reset_type: u32, cookie: u32) -> Result<(), Error> {
-    success_or_error_32(C::call32(PSCI_SYSTEM_RESET2_32, [reset_type, cookie, 0, 0, 0, 0, 0])[0])
-}
+    /// Returns the amount of time in microseconds that the platform has spent in the given power
+    /// state since cold boot.
+    pub fn stat_residency_32<C: Call>(target_cpu: u32, power_state: u32) -> u32;
 
-/// Enables or disables memory protection.
-pub fn mem_protect<C: Call>(enable: bool) -> Result<bool, Error> {
-    match C::call32(PSCI_MEM_PROTECT, [enable as u32, 0, 0, 0, 0, 0, 0])[0] as i32 {
-        0 => Ok(false),
-        1 => Ok(true),
-        error => Err(error.into()),
-    }
-}
+    /// Returns the number of times the platform has used the given power state since cold boot.
+    pub fn stat_count<C: Call64>(target_cpu: u64, power_state: u32) -> u64;
 
-/// Checks whether a memory range is protected by `MEM_PROTECT`.
-pub fn mem_protect_check_range<C: Call>(base: u64, length: u64) -> Result<(), Error> {
-    success_or_error_64(
-        C::call64(
-            PSCI_MEM_PROTECT_CHECK_RANGE_64,
-            [base, length, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
-        )[0],
-    )
-}
+    /// Returns the number of times the platform has used the given power state since cold boot.
+    pub fn stat_count_32<C: Call>(target_cpu: u32, power_state: u32) -> u32;
 
-/// Checks whether a memory range is protected by `MEM_PROTECT`.
-pub fn mem_protect_check_range_32<C: Call>(base: u32, length: u32) -> Result<(), Error> {
-    success_or_error_32(
-        C::call32(
-            PSCI_MEM_PROTECT_CHECK_RANGE_32,
-            [base, length, 0, 0, 0, 0, 0],
-        )[0],
-    )
-}
+    /// Enables or disables memory protection.
+    pub fn mem_protect<C: Call>(enable: bool) -> Result<bool, Error>;
 
-/// Queries whether `SMCCC_VERSION` or a specific PSCI function is implemented, and what features
-/// are supported.
-pub fn psci_features<C: Call>(psci_function_id: u32) -> Result<u32, Error> {
-    positive_or_error_32(C::call32(PSCI_FEATURES, [psci_function_id, 0, 0, 0, 0, 0, 0])[0])
-}
-
-/// Puts the current core into an implementation-defined low power state.
-pub fn cpu_freeze<C: Call>() -> Result<(), Error> {
-    success_or_error_32(C::call32(PSCI_CPU_FREEZE, [0; 7])[0])
-}
+    /// Returns the format of the `power_state` parameter which the firmware uses for `CPU_SUSPEND`,
+    /// `STAT_RESIDENCY` and `STAT_COUNT`, as reported by `PSCI_FEATURES`.
+    ///
+    /// This queries the features of `CPU_SUSPEND_64`, or `CPU_SUSPEND_32` if the firmware doesn't
+    /// implement the SMC64 version.
+    pub fn power_state_format<C: Call>() -> Result<PowerStateFormat, Error>;
 
-/// Puts the current core into an implementation-defined low power state.
-pub fn cpu_default_suspend<C: Call>(
-    entry_point_address: u64,
-    context_id: u64,
-) -> Result<(), Error> {
-    success_or_error_64(
-        C::call64(
-            PSCI_CPU_DEFAULT_SUSPEND_64,
-            [
-                entry_point_address,
-                context_id,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-            ],
-        )[0],
-    )
+    /// Queries whether a specific PSCI function is implemented, and decodes the features which it
+    /// supports.
+    pub fn function_features<C: Call>(psci_function_id: FunctionId) -> Result<Features, Error>;
 }
 
-/// Puts the current core into an implementation-defined low power state.
-pub fn cpu_default_suspend_32<C: Call>(
-    entry_point_address: u32,
-    context_id: u32,
-) -> Result<(), Error> {
-    success_or_error_32(
-        C::call32(
-            PSCI_CPU_DEFAULT_SUSPEND_32,
-            [entry_point_address, context_id, 0, 0, 0, 0, 0],
-        )[0],
-    )
-}
-
-/// Retuns the true hardware state of a node in the power domain topology.
-pub fn node_hw_state<C: Call>(target_cpu: u64, power_level: u32) -> Result<PowerState, Error> {
-    (C::call64(
-        PSCI_NODE_HW_STATE_64,
-        [
-            target_cpu,
-            power_level.into(),
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-        ],
-    )[0] as i32)
-        .try_into()
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::Mock,
+        psci::{PSCI_CPU_ON_32, PSCI_CPU_ON_64, PSCI_FEATURES, PSCI_VERSION},
+    };
 
-/// Retuns the true hardware state of a node in the power domain topology.
-pub fn node_hw_state_32<C: Call>(target_cpu: u32, power_level: u32) -> Result<PowerState, Error> {
-    (C::call32(
-        PSCI_NODE_HW_STATE_32,
-        [target_cpu, power_level, 0, 0, 0, 0, 0],
-    )[0] as i32)
-        .try_into()
-}
-
-/// Suspends the system to RAM.
-pub fn system_suspend<C: Call>(entry_point_address: u64, context_id: u64) -> Result<(), Error> {
-    success_or_error_64(
-        C::call64(
-            PSCI_SYSTEM_SUSPEND_64,
-            [
-                entry_point_address,
-                context_id,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-            ],
-        )[0],
-    )
-}
-
-/// Suspends the system to RAM.
-pub fn system_suspend_32<C: Call>(entry_point_address: u32, context_id: u32) -> Result<(), Error> {
-    success_or_error_32(
-        C::call32(
-            PSCI_SYSTEM_SUSPEND_32,
-            [entry_point_address, context_id, 0, 0, 0, 0, 0],
-        )[0],
-    )
-}
-
-/// Sets the mode used by `CPU_SUSPEND`.
-pub fn set_suspend_mode<C: Call>(mode: SuspendMode) -> Result<(), Error> {
-    success_or_error_32(C::call32(PSCI_SET_SUSPEND_MODE, [mode.into(), 0, 0, 0, 0, 0, 0])[0])
-}
-
-/// Returns the amount of time in microseconds that the platform has spent in the given power state
-/// since cold boot.
-pub fn stat_residency<C: Call>(target_cpu: u64, power_state: u32) -> u64 {
-    C::call64(
-        PSCI_STAT_RESIDENCY_64,
-        [
-            target_cpu,
-            power_state.into(),
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-        ],
-    )[0]
-}
-
-/// Returns the amount of time in microseconds that the platform has spent in the given power state
-/// since cold boot.
-pub fn stat_residency_32<C: Call>(target_cpu: u32, power_state: u32) -> u32 {
-    C::call32(
-        PSCI_STAT_RESIDENCY_32,
-        [target_cpu, power_state, 0, 0, 0, 0, 0],
-    )[0]
-}
-
-/// Returns the number of times the platform has used the given power state since cold boot.
-pub fn stat_count<C: Call>(target_cpu: u64, power_state: u32) -> u64 {
-    C::call64(
-        PSCI_STAT_COUNT_64,
-        [
-            target_cpu,
-            power_state.into(),
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-        ],
-    )[0]
-}
-
-/// Returns the number of times the platform has used the given power state since cold boot.
-pub fn stat_count_32<C: Call>(target_cpu: u32, power_state: u32) -> u32 {
-    C::call32(PSCI_STAT_COUNT_32, [target_cpu, power_state, 0, 0, 0, 0, 0])[0]
+    #[test]
+    fn forwards_to_backend() {
+        Mock::expect_call32(PSCI_VERSION, &[], &[0x0001_0001]);
+        assert_eq!(version::<Mock>(), Ok(Version { major: 1, minor: 1 }));
+        Mock::expect_call64(PSCI_CPU_ON_64, &[1, 0x8000_0000, 42], &[-3i64 as u64]);
+        assert_eq!(cpu_on::<Mock>(1, 0x8000_0000, 42), Err(Error::Denied));
+        Mock::expect_call32(PSCI_CPU_ON_32, &[2, 0x8000_0000, 42], &[0]);
+        assert_eq!(cpu_on_32::<Mock>(2, 0x8000_0000, 42), Ok(()));
+        Mock::expect_call32(PSCI_FEATURES, &[PSCI_CPU_ON_64.into()], &[0]);
+        assert_eq!(psci_features::<Mock>(PSCI_CPU_ON_64), Ok(0));
+        Mock::finish();
+    }
 }
@@ -0,0 +1,385 @@
+// Copyright 2026 the authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Discovery of the PSCI conduit from a flattened devicetree.
+//!
+//! This looks at the `/psci` node described by the devicetree binding for PSCI, reading its
+//! `method` property to choose between HVC and SMC and its `compatible` property to find which
+//! version of PSCI is present. A node whose `status` property is anything other than `okay` is
+//! ignored. It doesn't allocate, and only needs the raw FDT blob.
+
+use crate::Conduit;
+
+const FDT_MAGIC: u32 = 0xd00d_feed;
+const FDT_HEADER_SIZE: usize = 40;
+/// The version of the FDT format which this parser understands. Version 17 is the first to include
+/// the size of the structure block in the header.
+const FDT_VERSION: u32 = 17;
+const FDT_BEGIN_NODE: u32 = 1;
+const FDT_END_NODE: u32 = 2;
+const FDT_PROP: u32 = 3;
+const FDT_NOP: u32 = 4;
+const FDT_END: u32 = 9;
+
+/// Errors finding the PSCI node in a flattened devicetree.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum Error {
+    /// The FDT header was missing or had the wrong magic number.
+    #[error("Invalid FDT header")]
+    InvalidHeader,
+    /// The FDT is in a version of the format which isn't compatible with version 17.
+    #[error("Unsupported FDT version {version} (compatible with {last_comp_version})")]
+    UnsupportedVersion {
+        /// The version of the format.
+        version: u32,
+        /// The oldest version of the format with which it is backwards compatible.
+        last_comp_version: u32,
+    },
+    /// The structure block of the FDT was malformed or extended past the end of the blob.
+    #[error("Malformed FDT structure block")]
+    Malformed,
+    /// There was no enabled `/psci` node.
+    #[error("No enabled /psci node in FDT")]
+    NodeNotFound,
+    /// The `/psci` node had no `method` property.
+    #[error("/psci node has no method property")]
+    MissingMethod,
+    /// The `method` property was something other than `hvc` or `smc`.
+    #[error("/psci node has invalid method property")]
+    InvalidMethod,
+    /// The `compatible` property was missing or didn't contain any known PSCI version.
+    #[error("/psci node is not compatible with any known PSCI version")]
+    Incompatible,
+}
+
+/// The most recent PSCI version listed in the `compatible` property of the PSCI node.
+#[derive(Copy, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Compatible {
+    /// `arm,psci`, i.e. PSCI 0.1 with implementation defined function IDs.
+    V0_1,
+    /// `arm,psci-0.2`.
+    V0_2,
+    /// `arm,psci-1.0`.
+    V1_0,
+}
+
+impl Compatible {
+    fn from_compatible(compatible: &[u8]) -> Option<Self> {
+        match compatible {
+            b"arm,psci" => Some(Self::V0_1),
+            b"arm,psci-0.2" => Some(Self::V0_2),
+            b"arm,psci-1.0" => Some(Self::V1_0),
+            _ => None,
+        }
+    }
+}
+
+/// The details of the PSCI implementation described by the devicetree.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct PsciNode {
+    /// The conduit to use for PSCI calls. This is never [`Conduit::None`].
+    pub conduit: Conduit,
+    /// The most recent PSCI version which the implementation claims to be compatible with.
+    pub compatible: Compatible,
+}
+
+/// Finds the `/psci` node in the given FDT blob and returns the conduit and PSCI version it
+/// describes.
+pub fn discover(fdt: &[u8]) -> Result<PsciNode, Error> {
+    let header = |index: usize| read_u32(fdt, index * 4).ok_or(Error::InvalidHeader);
+    if fdt.len() < FDT_HEADER_SIZE || header(0)? != FDT_MAGIC {
+        return Err(Error::InvalidHeader);
+    }
+    let version = header(5)?;
+    let last_comp_version = header(6)?;
+    if version < FDT_VERSION || last_comp_version > FDT_VERSION {
+        return Err(Error::UnsupportedVersion {
+            version,
+            last_comp_version,
+        });
+    }
+    let total_size = header(1)? as usize;
+    let struct_offset = header(2)? as usize;
+    let strings_offset = header(3)? as usize;
+    let strings_size = header(8)? as usize;
+    let struct_size = header(9)? as usize;
+    let fdt = fdt.get(..total_size).ok_or(Error::InvalidHeader)?;
+    let structure = slice(fdt, struct_offset, struct_size).ok_or(Error::InvalidHeader)?;
+    let strings = slice(fdt, strings_offset, strings_size).ok_or(Error::InvalidHeader)?;
+
+    let mut offset = 0;
+    let mut depth = 0usize;
+    let mut in_psci = false;
+    let mut method = None;
+    let mut compatible = None;
+    let mut enabled = true;
+    loop {
+        let token = read_u32(structure, offset).ok_or(Error::Malformed)?;
+        offset += 4;
+        match token {
+            FDT_BEGIN_NODE => {
+                let name = read_cstr(structure, offset).ok_or(Error::Malformed)?;
+                offset = align4(offset + name.len() + 1);
+                depth += 1;
+                if depth == 2 && (name == b"psci" || name.starts_with(b"psci@")) {
+                    in_psci = true;
+                }
+            }
+            FDT_END_NODE => {
+                if in_psci && depth == 2 {
+                    if enabled {
+                        return Ok(PsciNode {
+                            conduit: method.ok_or(Error::MissingMethod)?,
+                            compatible: compatible.ok_or(Error::Incompatible)?,
+                        });
+                    }
+                    // Keep looking in case there is another PSCI node which is enabled.
+                    in_psci = false;
+                    method = None;
+                    compatible = None;
+                    enabled = true;
+                }
+                depth = depth.checked_sub(1).ok_or(Error::Malformed)?;
+            }
+            FDT_PROP => {
+                let len = read_u32(structure, offset).ok_or(Error::Malformed)? as usize;
+                let name_offset = read_u32(structure, offset + 4).ok_or(Error::Malformed)? as usize;
+                offset += 8;
+                let value = slice(structure, offset, len).ok_or(Error::Malformed)?;
+                offset = align4(offset + len);
+                if in_psci && depth == 2 {
+                    match read_cstr(strings, name_offset).ok_or(Error::Malformed)? {
+                        b"method" => {
+                            method = Some(match value {
+                                b"hvc\0" => Conduit::Hvc,
+                                b"smc\0" => Conduit::Smc,
+                                _ => return Err(Error::InvalidMethod),
+                            });
+                        }
+                        b"compatible" => {
+                            compatible = value
+                                .split(|&b| b == 0)
+                                .filter_map(Compatible::from_compatible)
+                                .max();
+                        }
+                        b"status" => {
+                            enabled = matches!(value, b"okay\0" | b"ok\0");
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            FDT_NOP => {}
+            FDT_END => return Err(Error::NodeNotFound),
+            _ => return Err(Error::Malformed),
+        }
+    }
+}
+
+fn slice(bytes: &[u8], offset: usize, len: usize) -> Option<&[u8]> {
+    bytes.get(offset..offset.checked_add(len)?)
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(
+        slice(bytes, offset, 4)?.try_into().ok()?,
+    ))
+}
+
+/// Returns the NUL-terminated string starting at the given offset, without its terminator.
+fn read_cstr(bytes: &[u8], offset: usize) -> Option<&[u8]> {
+    let bytes = bytes.get(offset..)?;
+    let len = bytes.iter().position(|&b| b == 0)?;
+    Some(&bytes[..len])
+}
+
+fn align4(offset: usize) -> usize {
+    offset.next_multiple_of(4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec::Vec;
+
+    /// The name and value of a property.
+    type Property<'a> = (&'a str, &'a [u8]);
+
+    /// Builds a minimal FDT with a root node containing a single child node with the given name
+    /// and properties.
+    fn build_fdt(node_name: &str, properties: &[Property]) -> Vec<u8> {
+        build_fdt_nodes(&[(node_name, properties)])
+    }
+
+    /// Builds a minimal FDT with a root node containing the given child nodes and their
+    /// properties.
+    fn build_fdt_nodes(nodes: &[(&str, &[Property])]) -> Vec<u8> {
+        let mut structure = Vec::new();
+        let mut strings = Vec::new();
+        let push_u32 =
+            |structure: &mut Vec<u8>, value: u32| structure.extend_from_slice(&value.to_be_bytes());
+        let push_name = |structure: &mut Vec<u8>, name: &str| {
+            structure.extend_from_slice(name.as_bytes());
+            structure.push(0);
+            structure.resize(align4(structure.len()), 0);
+        };
+
+        push_u32(&mut structure, FDT_BEGIN_NODE);
+        push_name(&mut structure, "");
+        for (node_name, properties) in nodes {
+            push_u32(&mut structure, FDT_BEGIN_NODE);
+            push_name(&mut structure, node_name);
+            for (name, value) in *properties {
+                push_u32(&mut structure, FDT_PROP);
+                push_u32(&mut structure, value.len() as u32);
+                push_u32(&mut structure, strings.len() as u32);
+                strings.extend_from_slice(name.as_bytes());
+                strings.push(0);
+                structure.extend_from_slice(value);
+                structure.resize(align4(structure.len()), 0);
+            }
+            push_u32(&mut structure, FDT_END_NODE);
+        }
+        push_u32(&mut structure, FDT_END_NODE);
+        push_u32(&mut structure, FDT_END);
+
+        let struct_offset = FDT_HEADER_SIZE + 16;
+        let strings_offset = struct_offset + structure.len();
+        let total_size = strings_offset + strings.len();
+        let mut fdt = Vec::new();
+        for value in [
+            FDT_MAGIC,
+            total_size as u32,
+            struct_offset as u32,
+            strings_offset as u32,
+            FDT_HEADER_SIZE as u32,
+            17,
+            16,
+            0,
+            strings.len() as u32,
+            structure.len() as u32,
+        ] {
+            fdt.extend_from_slice(&value.to_be_bytes());
+        }
+        // Empty memory reservation block.
+        fdt.extend_from_slice(&[0; 16]);
+        fdt.extend_from_slice(&structure);
+        fdt.extend_from_slice(&strings);
+        fdt
+    }
+
+    #[test]
+    fn discover_psci() {
+        let fdt = build_fdt(
+            "psci",
+            &[
+                ("compatible", b"arm,psci-1.0\0arm,psci-0.2\0arm,psci\0"),
+                ("method", b"smc\0"),
+            ],
+        );
+        assert_eq!(
+            discover(&fdt),
+            Ok(PsciNode {
+                conduit: Conduit::Smc,
+                compatible: Compatible::V1_0,
+            })
+        );
+
+        let fdt = build_fdt(
+            "psci",
+            &[("method", b"hvc\0"), ("compatible", b"arm,psci-0.2\0")],
+        );
+        assert_eq!(
+            discover(&fdt),
+            Ok(PsciNode {
+                conduit: Conduit::Hvc,
+                compatible: Compatible::V0_2,
+            })
+        );
+    }
+
+    #[test]
+    fn discover_errors() {
+        assert_eq!(discover(&[0; 8]), Err(Error::InvalidHeader));
+        assert_eq!(
+            discover(&build_fdt("cpus", &[("method", b"smc\0")])),
+            Err(Error::NodeNotFound)
+        );
+        assert_eq!(
+            discover(&build_fdt("psci", &[("compatible", b"arm,psci-1.0\0")])),
+            Err(Error::MissingMethod)
+        );
+        assert_eq!(
+            discover(&build_fdt(
+                "psci",
+                &[("compatible", b"arm,psci-1.0\0"), ("method", b"svc\0")]
+            )),
+            Err(Error::InvalidMethod)
+        );
+        assert_eq!(
+            discover(&build_fdt(
+                "psci",
+                &[("compatible", b"foo,bar\0"), ("method", b"hvc\0")]
+            )),
+            Err(Error::Incompatible)
+        );
+        let mut fdt = build_fdt("psci", &[("method", b"hvc\0")]);
+        fdt.truncate(fdt.len() - 4);
+        assert_eq!(discover(&fdt), Err(Error::InvalidHeader));
+    }
+
+    #[test]
+    fn unsupported_version() {
+        let mut fdt = build_fdt("psci", &[("method", b"hvc\0")]);
+        // Version 16, which has no structure block size.
+        fdt[20..24].copy_from_slice(&16u32.to_be_bytes());
+        assert_eq!(
+            discover(&fdt),
+            Err(Error::UnsupportedVersion {
+                version: 16,
+                last_comp_version: 16,
+            })
+        );
+        // A future version which isn't backwards compatible with version 17.
+        fdt[20..24].copy_from_slice(&19u32.to_be_bytes());
+        fdt[24..28].copy_from_slice(&18u32.to_be_bytes());
+        assert_eq!(
+            discover(&fdt),
+            Err(Error::UnsupportedVersion {
+                version: 19,
+                last_comp_version: 18,
+            })
+        );
+    }
+
+    #[test]
+    fn skip_disabled_node() {
+        let disabled: &[Property] = &[
+            ("compatible", b"arm,psci-1.0\0"),
+            ("method", b"smc\0"),
+            ("status", b"disabled\0"),
+        ];
+        assert_eq!(
+            discover(&build_fdt("psci", disabled)),
+            Err(Error::NodeNotFound)
+        );
+        assert_eq!(
+            discover(&build_fdt_nodes(&[
+                ("psci", disabled),
+                (
+                    "psci@1",
+                    &[
+                        ("compatible", b"arm,psci-0.2\0"),
+                        ("method", b"hvc\0"),
+                        ("status", b"okay\0"),
+                    ],
+                ),
+            ])),
+            Ok(PsciNode {
+                conduit: Conduit::Hvc,
+                compatible: Compatible::V0_2,
+            })
+        );
+    }
+}
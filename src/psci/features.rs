@@ -0,0 +1,167 @@
+// Copyright 2026 the authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Typed feature flags returned by `PSCI_FEATURES`.
+
+use super::{
+    OffType, PSCI_CPU_SUSPEND_32, PSCI_CPU_SUSPEND_64, PSCI_SYSTEM_OFF2_32, PSCI_SYSTEM_OFF2_64,
+    PowerStateFormat,
+};
+use crate::FunctionId;
+
+/// The bit in the `CPU_SUSPEND` feature flags which indicates that OS-initiated mode is supported.
+const OS_INITIATED_MODE: u32 = 1 << 0;
+/// The bit in the `CPU_SUSPEND` feature flags which indicates the extended StateID format.
+const EXTENDED_STATE_ID: u32 = 1 << 1;
+/// The bit in the `SYSTEM_OFF2` feature flags which indicates that `HIBERNATE_OFF` is supported.
+const HIBERNATE_OFF: u32 = 1 << 0;
+
+/// The features of `CPU_SUSPEND`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct CpuSuspendFeatures {
+    /// Whether OS-initiated mode is supported.
+    pub os_initiated_mode: bool,
+    /// The format of the `power_state` parameter.
+    pub power_state_format: PowerStateFormat,
+}
+
+impl From<u32> for CpuSuspendFeatures {
+    fn from(flags: u32) -> Self {
+        Self {
+            os_initiated_mode: flags & OS_INITIATED_MODE != 0,
+            power_state_format: if flags & EXTENDED_STATE_ID != 0 {
+                PowerStateFormat::Extended
+            } else {
+                PowerStateFormat::Original
+            },
+        }
+    }
+}
+
+impl From<CpuSuspendFeatures> for u32 {
+    fn from(features: CpuSuspendFeatures) -> Self {
+        let mut flags = 0;
+        if features.os_initiated_mode {
+            flags |= OS_INITIATED_MODE;
+        }
+        if features.power_state_format == PowerStateFormat::Extended {
+            flags |= EXTENDED_STATE_ID;
+        }
+        flags
+    }
+}
+
+/// The features of `SYSTEM_OFF2`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SystemOff2Features {
+    /// Whether [`OffType::HibernateOff`] is supported.
+    pub hibernate_off: bool,
+}
+
+impl SystemOff2Features {
+    /// Returns whether the given type of power off is supported.
+    pub fn supports(&self, off_type: OffType) -> bool {
+        match off_type {
+            OffType::HibernateOff => self.hibernate_off,
+        }
+    }
+}
+
+impl From<u32> for SystemOff2Features {
+    fn from(flags: u32) -> Self {
+        Self {
+            hibernate_off: flags & HIBERNATE_OFF != 0,
+        }
+    }
+}
+
+impl From<SystemOff2Features> for u32 {
+    fn from(features: SystemOff2Features) -> Self {
+        if features.hibernate_off {
+            HIBERNATE_OFF
+        } else {
+            0
+        }
+    }
+}
+
+/// The features of an implemented function, as returned by `PSCI_FEATURES`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Features {
+    /// The features of `CPU_SUSPEND_32` or `CPU_SUSPEND_64`.
+    CpuSuspend(CpuSuspendFeatures),
+    /// The features of `SYSTEM_OFF2_32` or `SYSTEM_OFF2_64`.
+    SystemOff2(SystemOff2Features),
+    /// The raw flags for a function which has no feature flags defined.
+    Other(u32),
+}
+
+impl Features {
+    /// Decodes the feature flags which `PSCI_FEATURES` returned for the given function.
+    pub fn decode(function: FunctionId, flags: u32) -> Self {
+        match function {
+            PSCI_CPU_SUSPEND_32 | PSCI_CPU_SUSPEND_64 => Self::CpuSuspend(flags.into()),
+            PSCI_SYSTEM_OFF2_32 | PSCI_SYSTEM_OFF2_64 => Self::SystemOff2(flags.into()),
+            _ => Self::Other(flags),
+        }
+    }
+}
+
+impl From<Features> for u32 {
+    fn from(features: Features) -> Self {
+        match features {
+            Features::CpuSuspend(features) => features.into(),
+            Features::SystemOff2(features) => features.into(),
+            Features::Other(flags) => flags,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::psci::PSCI_SYSTEM_OFF;
+
+    #[test]
+    fn cpu_suspend_flags_round_trip() {
+        for (flags, os_initiated_mode, power_state_format) in [
+            (0b00, false, PowerStateFormat::Original),
+            (0b01, true, PowerStateFormat::Original),
+            (0b10, false, PowerStateFormat::Extended),
+            (0b11, true, PowerStateFormat::Extended),
+        ] {
+            let features = Features::CpuSuspend(CpuSuspendFeatures {
+                os_initiated_mode,
+                power_state_format,
+            });
+            assert_eq!(Features::decode(PSCI_CPU_SUSPEND_64, flags), features);
+            assert_eq!(Features::decode(PSCI_CPU_SUSPEND_32, flags), features);
+            assert_eq!(u32::from(features), flags);
+        }
+    }
+
+    #[test]
+    fn system_off2_flags_round_trip() {
+        for (flags, hibernate_off) in [(0, false), (1, true)] {
+            let features = SystemOff2Features { hibernate_off };
+            assert_eq!(
+                Features::decode(PSCI_SYSTEM_OFF2_64, flags),
+                Features::SystemOff2(features)
+            );
+            assert_eq!(
+                Features::decode(PSCI_SYSTEM_OFF2_32, flags),
+                Features::SystemOff2(features)
+            );
+            assert_eq!(u32::from(features), flags);
+            assert_eq!(features.supports(OffType::HibernateOff), hibernate_off);
+        }
+    }
+
+    #[test]
+    fn other_flags_round_trip() {
+        let features = Features::decode(PSCI_SYSTEM_OFF, 0x42);
+        assert_eq!(features, Features::Other(0x42));
+        assert_eq!(u32::from(features), 0x42);
+    }
+}
@@ -0,0 +1,385 @@
+// Copyright 2026 the authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! PSCI calls with native width arguments, which pick the SMC32 or SMC64 version of each function
+//! to match the target.
+//!
+//! For example, this brings up a secondary core using `CPU_ON_64` on aarch64 or `CPU_ON_32` on
+//! 32-bit Arm, or `CPU_ON_32` on aarch64 if the firmware only implements the SMC32 functions:
+//!
+//! ```
+//! use smccc::psci::{
+//!     Error,
+//!     native::{Native, NativeBackend},
+//! };
+//!
+//! fn start_secondary<C: NativeBackend>(
+//!     conduit: C,
+//!     mpidr: usize,
+//!     entry_point: usize,
+//! ) -> Result<(), Error> {
+//!     let psci = Native::detect(conduit);
+//!     psci.cpu_on(mpidr, entry_point, 0)
+//! }
+//!
+//! # #[cfg(target_arch = "aarch64")]
+//! # fn start_via_smc(mpidr: usize, entry_point: usize) -> Result<(), Error> {
+//! start_secondary(smccc::Smc, mpidr, entry_point)
+//! # }
+//! ```
+
+use super::{
+    AffinityState, LowestAffinityLevel, OffType, PSCI_CPU_ON_32, PSCI_CPU_ON_64, PowerState,
+    Version, backend, error::Error,
+};
+#[cfg(target_pointer_width = "32")]
+use crate::Backend;
+#[cfg(target_pointer_width = "64")]
+use crate::Backend64;
+use crate::Convention;
+
+/// A backend which can make PSCI calls with the native width of the target.
+///
+/// On 64-bit targets this requires [`Backend64`], while on 32-bit targets any
+/// [`Backend`](crate::Backend) will do.
+#[cfg(target_pointer_width = "64")]
+pub trait NativeBackend: Backend64 {}
+
+#[cfg(target_pointer_width = "64")]
+impl<B: Backend64 + ?Sized> NativeBackend for B {}
+
+/// A backend which can make PSCI calls with the native width of the target.
+///
+/// On 64-bit targets this requires [`Backend64`](crate::Backend64), while on 32-bit targets any
+/// [`Backend`] will do.
+#[cfg(target_pointer_width = "32")]
+pub trait NativeBackend: Backend {}
+
+#[cfg(target_pointer_width = "32")]
+impl<B: Backend + ?Sized> NativeBackend for B {}
+
+/// The calling convention which matches the width of the target.
+#[cfg(target_pointer_width = "64")]
+const NATIVE_CONVENTION: Convention = Convention::Smc64;
+#[cfg(target_pointer_width = "32")]
+const NATIVE_CONVENTION: Convention = Convention::Smc32;
+
+/// Makes PSCI calls via the given backend, using the SMC64 functions on 64-bit targets and the
+/// SMC32 functions on 32-bit targets.
+///
+/// Firmware for 64-bit targets which only implements the SMC32 functions may be used with
+/// [`Native::detect`] or [`Native::with_convention`]. Arguments which don't fit in 32 bits then
+/// return [`Error::InvalidParameters`] without making a call.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Native<C> {
+    conduit: C,
+    convention: Convention,
+}
+
+impl<C: NativeBackend> Native<C> {
+    /// Makes calls via the given conduit, with the calling convention matching the target.
+    pub const fn new(conduit: C) -> Self {
+        Self {
+            conduit,
+            convention: NATIVE_CONVENTION,
+        }
+    }
+
+    /// Makes calls via the given conduit, with the given calling convention.
+    ///
+    /// On 32-bit targets the convention is ignored, as the SMC64 functions can't be called.
+    pub const fn with_convention(conduit: C, convention: Convention) -> Self {
+        #[cfg(target_pointer_width = "32")]
+        let convention = {
+            let _ = convention;
+            Convention::Smc32
+        };
+        Self {
+            conduit,
+            convention,
+        }
+    }
+
+    /// Makes calls via the given conduit, using the SMC32 functions only if the firmware reports
+    /// via `PSCI_FEATURES` that it implements `CPU_ON_32` but not `CPU_ON_64`.
+    ///
+    /// Firmware implementing PSCI before version 1.0 doesn't support `PSCI_FEATURES`, so is assumed
+    /// to implement the functions matching the target.
+    pub fn detect(conduit: C) -> Self {
+        let mut convention = NATIVE_CONVENTION;
+        if convention == Convention::Smc64
+            && backend::version(&conduit)
+                .is_ok_and(|version| version >= Version { major: 1, minor: 0 })
+            && backend::psci_features(&conduit, PSCI_CPU_ON_64).is_err()
+            && backend::psci_features(&conduit, PSCI_CPU_ON_32).is_ok()
+        {
+            convention = Convention::Smc32;
+        }
+        Self::with_convention(conduit, convention)
+    }
+
+    /// Returns the calling convention used for functions which have both SMC32 and SMC64 versions.
+    pub fn convention(&self) -> Convention {
+        self.convention
+    }
+
+    /// Returns the conduit used for calls, e.g. to make calls which only have an SMC32 version.
+    pub fn conduit(&self) -> &C {
+        &self.conduit
+    }
+
+    /// Suspends execution of a core or topology node.
+    pub fn cpu_suspend(
+        &self,
+        power_state: u32,
+        entry_point_address: usize,
+        context_id: usize,
+    ) -> Result<(), Error> {
+        #[cfg(target_pointer_width = "64")]
+        if self.convention == Convention::Smc64 {
+            return backend::cpu_suspend(
+                &self.conduit,
+                power_state,
+                entry_point_address as u64,
+                context_id as u64,
+            );
+        }
+        backend::cpu_suspend_32(
+            &self.conduit,
+            power_state,
+            narrow(entry_point_address)?,
+            narrow(context_id)?,
+        )
+    }
+
+    /// Powers up a core.
+    pub fn cpu_on(
+        &self,
+        target_cpu: usize,
+        entry_point_address: usize,
+        context_id: usize,
+    ) -> Result<(), Error> {
+        #[cfg(target_pointer_width = "64")]
+        if self.convention == Convention::Smc64 {
+            return backend::cpu_on(
+                &self.conduit,
+                target_cpu as u64,
+                entry_point_address as u64,
+                context_id as u64,
+            );
+        }
+        backend::cpu_on_32(
+            &self.conduit,
+            narrow(target_cpu)?,
+            narrow(entry_point_address)?,
+            narrow(context_id)?,
+        )
+    }
+
+    /// Gets the status of an affinity instance.
+    pub fn affinity_info(
+        &self,
+        target_affinity: usize,
+        lowest_affinity_level: LowestAffinityLevel,
+    ) -> Result<AffinityState, Error> {
+        #[cfg(target_pointer_width = "64")]
+        if self.convention == Convention::Smc64 {
+            return backend::affinity_info(
+                &self.conduit,
+                target_affinity as u64,
+                lowest_affinity_level,
+            );
+        }
+        backend::affinity_info_32(
+            &self.conduit,
+            narrow(target_affinity)?,
+            lowest_affinity_level,
+        )
+    }
+
+    /// Asks the Trusted OS to migrate its context to a specific core.
+    pub fn migrate(&self, target_cpu: usize) -> Result<(), Error> {
+        #[cfg(target_pointer_width = "64")]
+        if self.convention == Convention::Smc64 {
+            return backend::migrate(&self.conduit, target_cpu as u64);
+        }
+        backend::migrate_32(&self.conduit, narrow(target_cpu)?)
+    }
+
+    /// Returns the MPIDR value of the current resident core of the Trusted OS.
+    pub fn migrate_info_up_cpu(&self) -> usize {
+        #[cfg(target_pointer_width = "64")]
+        if self.convention == Convention::Smc64 {
+            return backend::migrate_info_up_cpu(&self.conduit) as usize;
+        }
+        backend::migrate_info_up_cpu_32(&self.conduit) as usize
+    }
+
+    /// Shuts down the system in the given way, e.g. to hibernate.
+    pub fn system_off2(&self, off_type: OffType, cookie: usize) -> Result<(), Error> {
+        #[cfg(target_pointer_width = "64")]
+        if self.convention == Convention::Smc64 {
+            return backend::system_off2(&self.conduit, off_type, cookie as u64);
+        }
+        backend::system_off2_32(&self.conduit, off_type, narrow(cookie)?)
+    }
+
+    /// Resets the system in an architectural or vendor-specific way.
+    pub fn system_reset2(&self, reset_type: u32, cookie: usize) -> Result<(), Error> {
+        #[cfg(target_pointer_width = "64")]
+        if self.convention == Convention::Smc64 {
+            return backend::system_reset2(&self.conduit, reset_type, cookie as u64);
+        }
+        backend::system_reset2_32(&self.conduit, reset_type, narrow(cookie)?)
+    }
+
+    /// Checks whether a memory range is protected by `MEM_PROTECT`.
+    pub fn mem_protect_check_range(&self, base: usize, length: usize) -> Result<(), Error> {
+        #[cfg(target_pointer_width = "64")]
+        if self.convention == Convention::Smc64 {
+            return backend::mem_protect_check_range(&self.conduit, base as u64, length as u64);
+        }
+        backend::mem_protect_check_range_32(&self.conduit, narrow(base)?, narrow(length)?)
+    }
+
+    /// Puts the current core into an implementation-defined low power state.
+    pub fn cpu_default_suspend(
+        &self,
+        entry_point_address: usize,
+        context_id: usize,
+    ) -> Result<(), Error> {
+        #[cfg(target_pointer_width = "64")]
+        if self.convention == Convention::Smc64 {
+            return backend::cpu_default_suspend(
+                &self.conduit,
+                entry_point_address as u64,
+                context_id as u64,
+            );
+        }
+        backend::cpu_default_suspend_32(
+            &self.conduit,
+            narrow(entry_point_address)?,
+            narrow(context_id)?,
+        )
+    }
+
+    /// Retuns the true hardware state of a node in the power domain topology.
+    pub fn node_hw_state(&self, target_cpu: usize, power_level: u32) -> Result<PowerState, Error> {
+        #[cfg(target_pointer_width = "64")]
+        if self.convention == Convention::Smc64 {
+            return backend::node_hw_state(&self.conduit, target_cpu as u64, power_level);
+        }
+        backend::node_hw_state_32(&self.conduit, narrow(target_cpu)?, power_level)
+    }
+
+    /// Suspends the system to RAM.
+    pub fn system_suspend(
+        &self,
+        entry_point_address: usize,
+        context_id: usize,
+    ) -> Result<(), Error> {
+        #[cfg(target_pointer_width = "64")]
+        if self.convention == Convention::Smc64 {
+            return backend::system_suspend(
+                &self.conduit,
+                entry_point_address as u64,
+                context_id as u64,
+            );
+        }
+        backend::system_suspend_32(
+            &self.conduit,
+            narrow(entry_point_address)?,
+            narrow(context_id)?,
+        )
+    }
+
+    /// Returns the amount of time in microseconds that the platform has spent in the given power
+    /// state since cold boot.
+    ///
+    /// The SMC32 version of the function returns only the low 32 bits.
+    pub fn stat_residency(&self, target_cpu: usize, power_state: u32) -> Result<u64, Error> {
+        #[cfg(target_pointer_width = "64")]
+        if self.convention == Convention::Smc64 {
+            return Ok(backend::stat_residency(
+                &self.conduit,
+                target_cpu as u64,
+                power_state,
+            ));
+        }
+        Ok(backend::stat_residency_32(&self.conduit, narrow(target_cpu)?, power_state).into())
+    }
+
+    /// Returns the number of times the platform has used the given power state since cold boot.
+    ///
+    /// The SMC32 version of the function returns only the low 32 bits.
+    pub fn stat_count(&self, target_cpu: usize, power_state: u32) -> Result<u64, Error> {
+        #[cfg(target_pointer_width = "64")]
+        if self.convention == Convention::Smc64 {
+            return Ok(backend::stat_count(
+                &self.conduit,
+                target_cpu as u64,
+                power_state,
+            ));
+        }
+        Ok(backend::stat_count_32(&self.conduit, narrow(target_cpu)?, power_state).into())
+    }
+}
+
+/// Converts a native width argument to 32 bits for an SMC32 call, or returns
+/// [`Error::InvalidParameters`] if it doesn't fit.
+fn narrow(value: usize) -> Result<u32, Error> {
+    value.try_into().map_err(|_| Error::InvalidParameters)
+}
+
+#[cfg(all(test, target_pointer_width = "64"))]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::Mock,
+        psci::{PSCI_AFFINITY_INFO_32, PSCI_FEATURES, PSCI_VERSION},
+    };
+
+    #[test]
+    fn native_uses_smc64() {
+        let psci = Native::new(Mock);
+        assert_eq!(psci.convention(), Convention::Smc64);
+        Mock::expect_call64(PSCI_CPU_ON_64, &[0x1_0000_0001, 0x8000_0000, 7], &[0]);
+        assert_eq!(psci.cpu_on(0x1_0000_0001, 0x8000_0000, 7), Ok(()));
+        Mock::finish();
+    }
+
+    #[test]
+    fn fallback_to_smc32() {
+        Mock::expect_call32(PSCI_VERSION, &[], &[0x0001_0001]);
+        Mock::expect_call32(PSCI_FEATURES, &[PSCI_CPU_ON_64.into()], &[-1i32 as u32]);
+        Mock::expect_call32(PSCI_FEATURES, &[PSCI_CPU_ON_32.into()], &[0]);
+        let psci = Native::detect(Mock);
+        assert_eq!(psci.convention(), Convention::Smc32);
+
+        Mock::expect_call32(PSCI_CPU_ON_32, &[1, 0x8000_0000, 7], &[0]);
+        assert_eq!(psci.cpu_on(1, 0x8000_0000, 7), Ok(()));
+        Mock::expect_call32(PSCI_AFFINITY_INFO_32, &[1, 0], &[1]);
+        assert_eq!(
+            psci.affinity_info(1, LowestAffinityLevel::All),
+            Ok(AffinityState::Off)
+        );
+        // Arguments too wide for SMC32 are rejected without making a call.
+        assert_eq!(
+            psci.cpu_on(1, 0x1_0000_0000, 7),
+            Err(Error::InvalidParameters)
+        );
+        Mock::finish();
+    }
+
+    #[test]
+    fn detect_keeps_smc64() {
+        // PSCI 0.2 doesn't have `PSCI_FEATURES`.
+        Mock::expect_call32(PSCI_VERSION, &[], &[0x0000_0002]);
+        assert_eq!(Native::detect(Mock).convention(), Convention::Smc64);
+        Mock::expect_call32(PSCI_VERSION, &[], &[0x0001_0000]);
+        Mock::expect_call32(PSCI_FEATURES, &[PSCI_CPU_ON_64.into()], &[0]);
+        assert_eq!(Native::detect(Mock).convention(), Convention::Smc64);
+        Mock::finish();
+    }
+}
@@ -0,0 +1,217 @@
+// Copyright 2026 the authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Types for building and parsing the `power_state` parameter to `CPU_SUSPEND`.
+
+use super::error::Error;
+
+/// The original format of `power_state` has the StateID in bits [15:0], the StateType in bit 16
+/// and the PowerLevel in bits [25:24].
+const ORIGINAL_STATE_ID_MASK: u32 = 0xffff;
+const ORIGINAL_STATE_TYPE_SHIFT: u32 = 16;
+const ORIGINAL_POWER_LEVEL_SHIFT: u32 = 24;
+const ORIGINAL_POWER_LEVEL_MASK: u32 = 0x3;
+
+/// The extended format of `power_state` has the StateID in bits [27:0] and the StateType in bit
+/// 30.
+const EXTENDED_STATE_ID_MASK: u32 = 0x0fff_ffff;
+const EXTENDED_STATE_TYPE_SHIFT: u32 = 30;
+
+/// The format of the `power_state` parameter used by the firmware, as reported by `PSCI_FEATURES`
+/// for `CPU_SUSPEND`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PowerStateFormat {
+    /// The original format, with StateID, StateType and PowerLevel fields.
+    Original,
+    /// The extended StateID format, with a larger StateID field and no PowerLevel.
+    Extended,
+}
+
+/// Whether a power state is a standby or retention state, or a power down state.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum StateType {
+    /// A standby or retention state.
+    Standby = 0,
+    /// A power down state.
+    PowerDown = 1,
+}
+
+/// A `power_state` parameter to `CPU_SUSPEND`, `STAT_RESIDENCY` or `STAT_COUNT`.
+///
+/// This must be encoded in the format which the firmware uses, as returned by
+/// [`power_state_format`](super::power_state_format).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PowerStateParam {
+    /// A power state in the original format.
+    Original {
+        /// Whether the state is a standby or power down state.
+        state_type: StateType,
+        /// The highest power level which will be powered down, from 0 to 3.
+        power_level: u8,
+        /// An implementation defined state identifier.
+        state_id: u16,
+    },
+    /// A power state in the extended StateID format.
+    Extended {
+        /// Whether the state is a standby or power down state.
+        state_type: StateType,
+        /// An implementation defined state identifier, which must fit in 28 bits.
+        state_id: u32,
+    },
+}
+
+impl PowerStateParam {
+    /// Returns the format of the power state.
+    pub fn format(&self) -> PowerStateFormat {
+        match self {
+            Self::Original { .. } => PowerStateFormat::Original,
+            Self::Extended { .. } => PowerStateFormat::Extended,
+        }
+    }
+
+    /// Returns whether the state is a standby or power down state.
+    pub fn state_type(&self) -> StateType {
+        match *self {
+            Self::Original { state_type, .. } | Self::Extended { state_type, .. } => state_type,
+        }
+    }
+
+    /// Encodes the power state as a raw parameter value, checking that it is in the given format
+    /// which the firmware uses.
+    ///
+    /// Returns [`Error::InvalidParameters`] if the power state is in a different format, or any
+    /// field is out of range.
+    pub fn encode(&self, format: PowerStateFormat) -> Result<u32, Error> {
+        if self.format() != format {
+            return Err(Error::InvalidParameters);
+        }
+        match *self {
+            Self::Original {
+                state_type,
+                power_level,
+                state_id,
+            } => {
+                let power_level = u32::from(power_level);
+                if power_level & !ORIGINAL_POWER_LEVEL_MASK != 0 {
+                    return Err(Error::InvalidParameters);
+                }
+                Ok((state_type as u32) << ORIGINAL_STATE_TYPE_SHIFT
+                    | power_level << ORIGINAL_POWER_LEVEL_SHIFT
+                    | u32::from(state_id))
+            }
+            Self::Extended {
+                state_type,
+                state_id,
+            } => {
+                if state_id & !EXTENDED_STATE_ID_MASK != 0 {
+                    return Err(Error::InvalidParameters);
+                }
+                Ok((state_type as u32) << EXTENDED_STATE_TYPE_SHIFT | state_id)
+            }
+        }
+    }
+
+    /// Parses a raw parameter value in the given format.
+    ///
+    /// Returns [`Error::InvalidParameters`] if any reserved bits are set.
+    pub fn decode(value: u32, format: PowerStateFormat) -> Result<Self, Error> {
+        let (state_type_shift, fields_mask) = match format {
+            PowerStateFormat::Original => (
+                ORIGINAL_STATE_TYPE_SHIFT,
+                ORIGINAL_STATE_ID_MASK
+                    | 1 << ORIGINAL_STATE_TYPE_SHIFT
+                    | ORIGINAL_POWER_LEVEL_MASK << ORIGINAL_POWER_LEVEL_SHIFT,
+            ),
+            PowerStateFormat::Extended => (
+                EXTENDED_STATE_TYPE_SHIFT,
+                EXTENDED_STATE_ID_MASK | 1 << EXTENDED_STATE_TYPE_SHIFT,
+            ),
+        };
+        if value & !fields_mask != 0 {
+            return Err(Error::InvalidParameters);
+        }
+        let state_type = if value & 1 << state_type_shift != 0 {
+            StateType::PowerDown
+        } else {
+            StateType::Standby
+        };
+        Ok(match format {
+            PowerStateFormat::Original => Self::Original {
+                state_type,
+                power_level: (value >> ORIGINAL_POWER_LEVEL_SHIFT & ORIGINAL_POWER_LEVEL_MASK)
+                    as u8,
+                state_id: (value & ORIGINAL_STATE_ID_MASK) as u16,
+            },
+            PowerStateFormat::Extended => Self::Extended {
+                state_type,
+                state_id: value & EXTENDED_STATE_ID_MASK,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn original_round_trip() {
+        let param = PowerStateParam::Original {
+            state_type: StateType::PowerDown,
+            power_level: 2,
+            state_id: 0x1234,
+        };
+        assert_eq!(param.encode(PowerStateFormat::Original), Ok(0x0201_1234));
+        assert_eq!(
+            PowerStateParam::decode(0x0201_1234, PowerStateFormat::Original),
+            Ok(param)
+        );
+        assert_eq!(
+            param.encode(PowerStateFormat::Extended),
+            Err(Error::InvalidParameters)
+        );
+        assert_eq!(
+            PowerStateParam::Original {
+                state_type: StateType::Standby,
+                power_level: 4,
+                state_id: 0,
+            }
+            .encode(PowerStateFormat::Original),
+            Err(Error::InvalidParameters)
+        );
+        assert_eq!(
+            PowerStateParam::decode(0x0002_0000, PowerStateFormat::Original),
+            Err(Error::InvalidParameters)
+        );
+    }
+
+    #[test]
+    fn extended_round_trip() {
+        let param = PowerStateParam::Extended {
+            state_type: StateType::PowerDown,
+            state_id: 0x0abc_def0,
+        };
+        assert_eq!(param.encode(PowerStateFormat::Extended), Ok(0x4abc_def0));
+        assert_eq!(
+            PowerStateParam::decode(0x4abc_def0, PowerStateFormat::Extended),
+            Ok(param)
+        );
+        assert_eq!(
+            param.encode(PowerStateFormat::Original),
+            Err(Error::InvalidParameters)
+        );
+        assert_eq!(
+            PowerStateParam::Extended {
+                state_type: StateType::Standby,
+                state_id: 0x1000_0000,
+            }
+            .encode(PowerStateFormat::Extended),
+            Err(Error::InvalidParameters)
+        );
+        assert_eq!(
+            PowerStateParam::decode(0x8000_0000, PowerStateFormat::Extended),
+            Err(Error::InvalidParameters)
+        );
+    }
+}
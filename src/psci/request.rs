@@ -0,0 +1,466 @@
+// Copyright 2026 the authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Decoding of incoming PSCI calls, for hypervisors and firmware which implement PSCI.
+
+use super::{
+    AffinityState, LowestAffinityLevel, MigrateType, OffType, PSCI_AFFINITY_INFO_32,
+    PSCI_AFFINITY_INFO_64, PSCI_CPU_DEFAULT_SUSPEND_32, PSCI_CPU_DEFAULT_SUSPEND_64,
+    PSCI_CPU_FREEZE, PSCI_CPU_OFF, PSCI_CPU_ON_32, PSCI_CPU_ON_64, PSCI_CPU_SUSPEND_32,
+    PSCI_CPU_SUSPEND_64, PSCI_FEATURES, PSCI_MEM_PROTECT, PSCI_MEM_PROTECT_CHECK_RANGE_32,
+    PSCI_MEM_PROTECT_CHECK_RANGE_64, PSCI_MIGRATE_32, PSCI_MIGRATE_64, PSCI_MIGRATE_INFO_TYPE,
+    PSCI_MIGRATE_INFO_UP_CPU_32, PSCI_MIGRATE_INFO_UP_CPU_64, PSCI_NODE_HW_STATE_32,
+    PSCI_NODE_HW_STATE_64, PSCI_SET_SUSPEND_MODE, PSCI_STAT_COUNT_32, PSCI_STAT_COUNT_64,
+    PSCI_STAT_RESIDENCY_32, PSCI_STAT_RESIDENCY_64, PSCI_SYSTEM_OFF, PSCI_SYSTEM_OFF2_32,
+    PSCI_SYSTEM_OFF2_64, PSCI_SYSTEM_RESET, PSCI_SYSTEM_RESET2_32, PSCI_SYSTEM_RESET2_64,
+    PSCI_SYSTEM_SUSPEND_32, PSCI_SYSTEM_SUSPEND_64, PSCI_VERSION, PowerState, SuspendMode, Version,
+    error::Error,
+};
+use crate::{Convention, FunctionId};
+
+/// A PSCI call, decoded from its function ID and argument registers.
+///
+/// The SMC32 and SMC64 versions of each function are represented by the same variant. Arguments
+/// of SMC32 calls are truncated to 32 bits before being zero-extended into the fields.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Request {
+    /// `PSCI_VERSION`
+    Version,
+    /// `CPU_SUSPEND`
+    CpuSuspend {
+        /// The power state to enter.
+        power_state: u32,
+        /// The address at which to resume execution, if the core is powered down.
+        entry_point: u64,
+        /// The value to pass in x0 or r0 when resuming at the entry point.
+        context_id: u64,
+    },
+    /// `CPU_OFF`
+    CpuOff,
+    /// `CPU_ON`
+    CpuOn {
+        /// The MPIDR of the core to power up.
+        target_cpu: u64,
+        /// The address at which the core should start execution.
+        entry_point: u64,
+        /// The value to pass in x0 or r0 when starting at the entry point.
+        context_id: u64,
+    },
+    /// `AFFINITY_INFO`
+    AffinityInfo {
+        /// The MPIDR of the affinity instance to query.
+        target_affinity: u64,
+        /// The lowest affinity level which is valid in `target_affinity`.
+        lowest_affinity_level: LowestAffinityLevel,
+    },
+    /// `MIGRATE`
+    Migrate {
+        /// The MPIDR of the core to which the Trusted OS should migrate.
+        target_cpu: u64,
+    },
+    /// `MIGRATE_INFO_TYPE`
+    MigrateInfoType,
+    /// `MIGRATE_INFO_UP_CPU`
+    MigrateInfoUpCpu,
+    /// `SYSTEM_OFF`
+    SystemOff,
+    /// `SYSTEM_RESET`
+    SystemReset,
+    /// `SYSTEM_RESET2`
+    SystemReset2 {
+        /// The architectural or vendor-specific type of reset.
+        reset_type: u32,
+        /// A vendor-specific value for vendor-specific resets.
+        cookie: u64,
+    },
+    /// `SYSTEM_OFF2`
+    SystemOff2 {
+        /// The type of power off.
+        off_type: OffType,
+        /// A value whose meaning depends on the type of power off.
+        cookie: u64,
+    },
+    /// `MEM_PROTECT`
+    MemProtect {
+        /// Whether to enable memory protection.
+        enable: bool,
+    },
+    /// `MEM_PROTECT_CHECK_RANGE`
+    MemProtectCheckRange {
+        /// The base address of the range to check.
+        base: u64,
+        /// The length of the range to check, in bytes.
+        length: u64,
+    },
+    /// `PSCI_FEATURES`
+    Features {
+        /// The function ID being queried, which may not be a valid PSCI function.
+        psci_function_id: FunctionId,
+    },
+    /// `CPU_FREEZE`
+    CpuFreeze,
+    /// `CPU_DEFAULT_SUSPEND`
+    CpuDefaultSuspend {
+        /// The address at which to resume execution, if the core is powered down.
+        entry_point: u64,
+        /// The value to pass in x0 or r0 when resuming at the entry point.
+        context_id: u64,
+    },
+    /// `NODE_HW_STATE`
+    NodeHwState {
+        /// The MPIDR of the node to query.
+        target_cpu: u64,
+        /// The power domain level of the node to query.
+        power_level: u32,
+    },
+    /// `SYSTEM_SUSPEND`
+    SystemSuspend {
+        /// The address at which to resume execution.
+        entry_point: u64,
+        /// The value to pass in x0 or r0 when resuming at the entry point.
+        context_id: u64,
+    },
+    /// `PSCI_SET_SUSPEND_MODE`
+    SetSuspendMode {
+        /// The mode to be used by `CPU_SUSPEND`.
+        mode: SuspendMode,
+    },
+    /// `PSCI_STAT_RESIDENCY`
+    StatResidency {
+        /// The MPIDR of the core to query.
+        target_cpu: u64,
+        /// The power state whose residency to query.
+        power_state: u32,
+    },
+    /// `PSCI_STAT_COUNT`
+    StatCount {
+        /// The MPIDR of the core to query.
+        target_cpu: u64,
+        /// The power state whose use count to query.
+        power_state: u32,
+    },
+}
+
+impl Request {
+    /// Decodes a PSCI call from its function ID and the argument registers x1 to x17.
+    ///
+    /// Returns [`Error::NotSupported`] if the function ID isn't a known PSCI function, or
+    /// [`Error::InvalidParameters`] if an enumerated argument has an unknown value.
+    pub fn parse(function: FunctionId, args: [u64; 17]) -> Result<Self, Error> {
+        let function = function.without_sve_hint();
+        let args = match function.convention() {
+            Convention::Smc32 => args.map(|arg| u64::from(arg as u32)),
+            Convention::Smc64 => args,
+        };
+        Ok(match function {
+            PSCI_VERSION => Self::Version,
+            PSCI_CPU_SUSPEND_32 | PSCI_CPU_SUSPEND_64 => Self::CpuSuspend {
+                power_state: args[0] as u32,
+                entry_point: args[1],
+                context_id: args[2],
+            },
+            PSCI_CPU_OFF => Self::CpuOff,
+            PSCI_CPU_ON_32 | PSCI_CPU_ON_64 => Self::CpuOn {
+                target_cpu: args[0],
+                entry_point: args[1],
+                context_id: args[2],
+            },
+            PSCI_AFFINITY_INFO_32 | PSCI_AFFINITY_INFO_64 => Self::AffinityInfo {
+                target_affinity: args[0],
+                lowest_affinity_level: args[1].try_into()?,
+            },
+            PSCI_MIGRATE_32 | PSCI_MIGRATE_64 => Self::Migrate {
+                target_cpu: args[0],
+            },
+            PSCI_MIGRATE_INFO_TYPE => Self::MigrateInfoType,
+            PSCI_MIGRATE_INFO_UP_CPU_32 | PSCI_MIGRATE_INFO_UP_CPU_64 => Self::MigrateInfoUpCpu,
+            PSCI_SYSTEM_OFF => Self::SystemOff,
+            PSCI_SYSTEM_RESET => Self::SystemReset,
+            PSCI_SYSTEM_OFF2_32 | PSCI_SYSTEM_OFF2_64 => Self::SystemOff2 {
+                off_type: args[0].try_into()?,
+                cookie: args[1],
+            },
+            PSCI_SYSTEM_RESET2_32 | PSCI_SYSTEM_RESET2_64 => Self::SystemReset2 {
+                reset_type: args[0] as u32,
+                cookie: args[1],
+            },
+            PSCI_MEM_PROTECT => Self::MemProtect {
+                enable: match args[0] {
+                    0 => false,
+                    1 => true,
+                    _ => return Err(Error::InvalidParameters),
+                },
+            },
+            PSCI_MEM_PROTECT_CHECK_RANGE_32 | PSCI_MEM_PROTECT_CHECK_RANGE_64 => {
+                Self::MemProtectCheckRange {
+                    base: args[0],
+                    length: args[1],
+                }
+            }
+            PSCI_FEATURES => Self::Features {
+                psci_function_id: FunctionId::new(args[0] as u32),
+            },
+            PSCI_CPU_FREEZE => Self::CpuFreeze,
+            PSCI_CPU_DEFAULT_SUSPEND_32 | PSCI_CPU_DEFAULT_SUSPEND_64 => Self::CpuDefaultSuspend {
+                entry_point: args[0],
+                context_id: args[1],
+            },
+            PSCI_NODE_HW_STATE_32 | PSCI_NODE_HW_STATE_64 => Self::NodeHwState {
+                target_cpu: args[0],
+                power_level: args[1] as u32,
+            },
+            PSCI_SYSTEM_SUSPEND_32 | PSCI_SYSTEM_SUSPEND_64 => Self::SystemSuspend {
+                entry_point: args[0],
+                context_id: args[1],
+            },
+            PSCI_SET_SUSPEND_MODE => Self::SetSuspendMode {
+                mode: args[0].try_into()?,
+            },
+            PSCI_STAT_RESIDENCY_32 | PSCI_STAT_RESIDENCY_64 => Self::StatResidency {
+                target_cpu: args[0],
+                power_state: args[1] as u32,
+            },
+            PSCI_STAT_COUNT_32 | PSCI_STAT_COUNT_64 => Self::StatCount {
+                target_cpu: args[0],
+                power_state: args[1] as u32,
+            },
+            _ => return Err(Error::NotSupported),
+        })
+    }
+}
+
+/// A successful response to a PSCI [`Request`].
+///
+/// Each kind of response may only be returned for the requests noted, which
+/// [`Response::encode`] checks.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Response {
+    /// The call succeeded with no other return value, e.g. for `CPU_ON`. This isn't valid for
+    /// functions which don't return on success, such as `CPU_OFF`.
+    Success,
+    /// The response to `PSCI_VERSION`.
+    Version(Version),
+    /// The response to `AFFINITY_INFO`.
+    AffinityState(AffinityState),
+    /// The response to `MIGRATE_INFO_TYPE`.
+    MigrateType(MigrateType),
+    /// The response to `MIGRATE_INFO_UP_CPU`.
+    Mpidr(u64),
+    /// The previous memory protection state, in response to `MEM_PROTECT`.
+    MemProtect(bool),
+    /// The feature flags returned by `PSCI_FEATURES`.
+    Features(u32),
+    /// The response to `NODE_HW_STATE`.
+    PowerState(PowerState),
+    /// The response to `PSCI_STAT_RESIDENCY` or `PSCI_STAT_COUNT`.
+    Stat(u64),
+}
+
+/// Error returned by [`Response::encode`] for a response which isn't valid for the request.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, thiserror::Error)]
+#[error("Response {response:?} is not valid for request {request:?}")]
+pub struct MismatchedResponse {
+    /// The request which was being handled.
+    pub request: Request,
+    /// The response which was given for it.
+    pub response: Response,
+}
+
+impl Response {
+    /// Returns whether this is a valid successful response to the given request.
+    pub fn is_valid_for(&self, request: &Request) -> bool {
+        match self {
+            Self::Success => matches!(
+                request,
+                Request::CpuSuspend { .. }
+                    | Request::CpuOn { .. }
+                    | Request::Migrate { .. }
+                    | Request::MemProtectCheckRange { .. }
+                    | Request::CpuDefaultSuspend { .. }
+                    | Request::SystemSuspend { .. }
+                    | Request::SetSuspendMode { .. }
+            ),
+            Self::Version(_) => matches!(request, Request::Version),
+            Self::AffinityState(_) => matches!(request, Request::AffinityInfo { .. }),
+            Self::MigrateType(_) => matches!(request, Request::MigrateInfoType),
+            Self::Mpidr(_) => matches!(request, Request::MigrateInfoUpCpu),
+            Self::MemProtect(_) => matches!(request, Request::MemProtect { .. }),
+            Self::Features(_) => matches!(request, Request::Features { .. }),
+            Self::PowerState(_) => matches!(request, Request::NodeHwState { .. }),
+            Self::Stat(_) => matches!(
+                request,
+                Request::StatResidency { .. } | Request::StatCount { .. }
+            ),
+        }
+    }
+
+    /// Encodes the result of handling the given request into the value to return in x0.
+    ///
+    /// For the SMC32 calling convention the value is truncated to 32 bits and then zero-extended.
+    /// Returns an error if the response isn't valid for the request, e.g. an
+    /// [`AffinityState`](Response::AffinityState) in response to `CPU_ON`. Any error may be returned
+    /// for any request.
+    pub fn encode(
+        request: &Request,
+        result: Result<Self, Error>,
+        convention: Convention,
+    ) -> Result<u64, MismatchedResponse> {
+        if let Ok(response) = result
+            && !response.is_valid_for(request)
+        {
+            return Err(MismatchedResponse {
+                request: *request,
+                response,
+            });
+        }
+        let value = match result {
+            Ok(Self::Success) => 0,
+            Ok(Self::Version(version)) => u32::from(version).into(),
+            Ok(Self::AffinityState(state)) => state as u64,
+            Ok(Self::MigrateType(migrate_type)) => migrate_type as u64,
+            Ok(Self::Mpidr(value) | Self::Stat(value)) => value,
+            Ok(Self::MemProtect(enabled)) => enabled.into(),
+            Ok(Self::Features(features)) => features.into(),
+            Ok(Self::PowerState(state)) => state as u64,
+            Err(error) => i64::from(error) as u64,
+        };
+        Ok(match convention {
+            Convention::Smc32 => u64::from(value as u32),
+            Convention::Smc64 => value,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[u64]) -> [u64; 17] {
+        let mut args = [0; 17];
+        args[..values.len()].copy_from_slice(values);
+        args
+    }
+
+    #[test]
+    fn parse_folds_conventions() {
+        assert_eq!(
+            Request::parse(PSCI_CPU_ON_64, args(&[0x1_0000_0001, 0x8000_0000, 42])),
+            Ok(Request::CpuOn {
+                target_cpu: 0x1_0000_0001,
+                entry_point: 0x8000_0000,
+                context_id: 42,
+            })
+        );
+        assert_eq!(
+            Request::parse(
+                PSCI_CPU_ON_32,
+                args(&[0x1_0000_0001, 0xffff_ffff_8000_0000, 42])
+            ),
+            Ok(Request::CpuOn {
+                target_cpu: 1,
+                entry_point: 0x8000_0000,
+                context_id: 42,
+            })
+        );
+        assert_eq!(
+            Request::parse(PSCI_SYSTEM_RESET2_64.with_sve_hint(), args(&[1, 2])),
+            Ok(Request::SystemReset2 {
+                reset_type: 1,
+                cookie: 2,
+            })
+        );
+        assert_eq!(
+            Request::parse(PSCI_SYSTEM_OFF2_64, args(&[1, 3])),
+            Ok(Request::SystemOff2 {
+                off_type: OffType::HibernateOff,
+                cookie: 3,
+            })
+        );
+        assert_eq!(
+            Request::parse(PSCI_FEATURES, args(&[0xffff_ffff_c400_0003])),
+            Ok(Request::Features {
+                psci_function_id: PSCI_CPU_ON_64,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Request::parse(PSCI_AFFINITY_INFO_64, args(&[0, 4])),
+            Err(Error::InvalidParameters)
+        );
+        assert_eq!(
+            Request::parse(PSCI_SYSTEM_OFF2_32, args(&[0, 0])),
+            Err(Error::InvalidParameters)
+        );
+        assert_eq!(
+            Request::parse(FunctionId::new(0x8400_00ff), args(&[])),
+            Err(Error::NotSupported)
+        );
+    }
+
+    #[test]
+    fn encode_response() {
+        assert_eq!(
+            Response::encode(
+                &Request::Version,
+                Ok(Response::Version(Version { major: 1, minor: 1 })),
+                Convention::Smc32
+            ),
+            Ok(0x0001_0001)
+        );
+        assert_eq!(
+            Response::encode(
+                &Request::MigrateInfoUpCpu,
+                Ok(Response::Mpidr(0x1_0000_0001)),
+                Convention::Smc64
+            ),
+            Ok(0x1_0000_0001)
+        );
+        assert_eq!(
+            Response::encode(
+                &Request::MigrateInfoUpCpu,
+                Ok(Response::Mpidr(0x1_0000_0001)),
+                Convention::Smc32
+            ),
+            Ok(1)
+        );
+        assert_eq!(
+            Response::encode(&Request::CpuOff, Err(Error::Denied), Convention::Smc64),
+            Ok(-3i64 as u64)
+        );
+        assert_eq!(
+            Response::encode(&Request::Version, Err(Error::AlreadyOn), Convention::Smc32),
+            Ok(0xffff_fffc)
+        );
+    }
+
+    #[test]
+    fn encode_mismatched_response() {
+        let request = Request::CpuOn {
+            target_cpu: 1,
+            entry_point: 0x8000_0000,
+            context_id: 0,
+        };
+        assert_eq!(
+            Response::encode(
+                &request,
+                Ok(Response::AffinityState(AffinityState::On)),
+                Convention::Smc64
+            ),
+            Err(MismatchedResponse {
+                request,
+                response: Response::AffinityState(AffinityState::On),
+            })
+        );
+        assert_eq!(
+            Response::encode(&Request::CpuOff, Ok(Response::Success), Convention::Smc32),
+            Err(MismatchedResponse {
+                request: Request::CpuOff,
+                response: Response::Success,
+            })
+        );
+    }
+}
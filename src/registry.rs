@@ -0,0 +1,399 @@
+// Copyright 2026 the authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Metadata about every function which the crate knows about, for diagnostics and dispatchers.
+
+use crate::{
+    Convention, FunctionId, arch,
+    arch::{
+        SMCCC_ARCH_FEATURES, SMCCC_ARCH_SOC_ID, SMCCC_ARCH_WORKAROUND_1, SMCCC_ARCH_WORKAROUND_2,
+        SMCCC_ARCH_WORKAROUND_3, SMCCC_VERSION,
+    },
+    psci,
+    psci::{
+        PSCI_AFFINITY_INFO_32, PSCI_AFFINITY_INFO_64, PSCI_CPU_DEFAULT_SUSPEND_32,
+        PSCI_CPU_DEFAULT_SUSPEND_64, PSCI_CPU_FREEZE, PSCI_CPU_OFF, PSCI_CPU_ON_32, PSCI_CPU_ON_64,
+        PSCI_CPU_SUSPEND_32, PSCI_CPU_SUSPEND_64, PSCI_FEATURES, PSCI_MEM_PROTECT,
+        PSCI_MEM_PROTECT_CHECK_RANGE_32, PSCI_MEM_PROTECT_CHECK_RANGE_64, PSCI_MIGRATE_32,
+        PSCI_MIGRATE_64, PSCI_MIGRATE_INFO_TYPE, PSCI_MIGRATE_INFO_UP_CPU_32,
+        PSCI_MIGRATE_INFO_UP_CPU_64, PSCI_NODE_HW_STATE_32, PSCI_NODE_HW_STATE_64,
+        PSCI_SET_SUSPEND_MODE, PSCI_STAT_COUNT_32, PSCI_STAT_COUNT_64, PSCI_STAT_RESIDENCY_32,
+        PSCI_STAT_RESIDENCY_64, PSCI_SYSTEM_OFF, PSCI_SYSTEM_OFF2_32, PSCI_SYSTEM_OFF2_64,
+        PSCI_SYSTEM_RESET, PSCI_SYSTEM_RESET2_32, PSCI_SYSTEM_RESET2_64, PSCI_SYSTEM_SUSPEND_32,
+        PSCI_SYSTEM_SUSPEND_64, PSCI_VERSION,
+    },
+};
+use core::fmt::{self, Display, Formatter};
+
+/// The specification which defines a function, and the version of it which introduced the
+/// function.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Spec {
+    /// The Arm Architecture Calls of the SMC Calling Convention.
+    Smccc(arch::Version),
+    /// The Power State Coordination Interface.
+    Psci(psci::Version),
+}
+
+impl Display for Spec {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Smccc(version) => write!(f, "SMCCC {version}"),
+            Self::Psci(version) => write!(f, "PSCI {version}"),
+        }
+    }
+}
+
+/// How a function uses the value it returns in w0 or x0.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Returns {
+    /// `SUCCESS`, or a negative error code.
+    Status,
+    /// A non-negative value, or a negative error code.
+    Value,
+    /// A value with no error codes, such as an MPIDR or a counter.
+    Raw,
+    /// Nothing if successful, as the function doesn't return. Otherwise a negative error code.
+    NoReturn,
+}
+
+/// Metadata about a function.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct FunctionInfo {
+    /// The function ID, without the SVE hint.
+    pub id: FunctionId,
+    /// The name of the function, as used for its constant in this crate, e.g. `PSCI_CPU_ON_64`.
+    pub name: &'static str,
+    /// The specification which defines the function, and the version which introduced it.
+    pub spec: Spec,
+    /// The number of argument registers which the function uses, after the function ID.
+    pub args: usize,
+    /// How the function uses its return value.
+    pub returns: Returns,
+}
+
+impl FunctionInfo {
+    const fn new(
+        id: FunctionId,
+        name: &'static str,
+        spec: Spec,
+        args: usize,
+        returns: Returns,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            spec,
+            args,
+            returns,
+        }
+    }
+
+    /// Returns whether this is the SMC32 or SMC64 form of the function.
+    pub const fn convention(&self) -> Convention {
+        self.id.convention()
+    }
+}
+
+const SMCCC_1_1: Spec = Spec::Smccc(arch::Version { major: 1, minor: 1 });
+const SMCCC_1_2: Spec = Spec::Smccc(arch::Version { major: 1, minor: 2 });
+const SMCCC_1_4: Spec = Spec::Smccc(arch::Version { major: 1, minor: 4 });
+const PSCI_0_2: Spec = Spec::Psci(psci::Version { major: 0, minor: 2 });
+const PSCI_1_0: Spec = Spec::Psci(psci::Version { major: 1, minor: 0 });
+const PSCI_1_1: Spec = Spec::Psci(psci::Version { major: 1, minor: 1 });
+const PSCI_1_3: Spec = Spec::Psci(psci::Version { major: 1, minor: 3 });
+
+/// All functions which the crate knows about.
+pub const FUNCTIONS: &[FunctionInfo] = {
+    use Returns::{NoReturn, Raw, Status, Value};
+    &[
+        FunctionInfo::new(SMCCC_VERSION, "SMCCC_VERSION", SMCCC_1_1, 0, Value),
+        FunctionInfo::new(
+            SMCCC_ARCH_FEATURES,
+            "SMCCC_ARCH_FEATURES",
+            SMCCC_1_1,
+            1,
+            Value,
+        ),
+        FunctionInfo::new(SMCCC_ARCH_SOC_ID, "SMCCC_ARCH_SOC_ID", SMCCC_1_2, 1, Value),
+        FunctionInfo::new(
+            SMCCC_ARCH_WORKAROUND_1,
+            "SMCCC_ARCH_WORKAROUND_1",
+            SMCCC_1_1,
+            0,
+            Status,
+        ),
+        FunctionInfo::new(
+            SMCCC_ARCH_WORKAROUND_2,
+            "SMCCC_ARCH_WORKAROUND_2",
+            SMCCC_1_1,
+            1,
+            Status,
+        ),
+        FunctionInfo::new(
+            SMCCC_ARCH_WORKAROUND_3,
+            "SMCCC_ARCH_WORKAROUND_3",
+            SMCCC_1_4,
+            0,
+            Status,
+        ),
+        FunctionInfo::new(PSCI_VERSION, "PSCI_VERSION", PSCI_0_2, 0, Value),
+        FunctionInfo::new(
+            PSCI_CPU_SUSPEND_32,
+            "PSCI_CPU_SUSPEND_32",
+            PSCI_0_2,
+            3,
+            Status,
+        ),
+        FunctionInfo::new(
+            PSCI_CPU_SUSPEND_64,
+            "PSCI_CPU_SUSPEND_64",
+            PSCI_0_2,
+            3,
+            Status,
+        ),
+        FunctionInfo::new(PSCI_CPU_OFF, "PSCI_CPU_OFF", PSCI_0_2, 0, NoReturn),
+        FunctionInfo::new(PSCI_CPU_ON_32, "PSCI_CPU_ON_32", PSCI_0_2, 3, Status),
+        FunctionInfo::new(PSCI_CPU_ON_64, "PSCI_CPU_ON_64", PSCI_0_2, 3, Status),
+        FunctionInfo::new(
+            PSCI_AFFINITY_INFO_32,
+            "PSCI_AFFINITY_INFO_32",
+            PSCI_0_2,
+            2,
+            Value,
+        ),
+        FunctionInfo::new(
+            PSCI_AFFINITY_INFO_64,
+            "PSCI_AFFINITY_INFO_64",
+            PSCI_0_2,
+            2,
+            Value,
+        ),
+        FunctionInfo::new(PSCI_MIGRATE_32, "PSCI_MIGRATE_32", PSCI_0_2, 1, Status),
+        FunctionInfo::new(PSCI_MIGRATE_64, "PSCI_MIGRATE_64", PSCI_0_2, 1, Status),
+        FunctionInfo::new(
+            PSCI_MIGRATE_INFO_TYPE,
+            "PSCI_MIGRATE_INFO_TYPE",
+            PSCI_0_2,
+            0,
+            Value,
+        ),
+        FunctionInfo::new(
+            PSCI_MIGRATE_INFO_UP_CPU_32,
+            "PSCI_MIGRATE_INFO_UP_CPU_32",
+            PSCI_0_2,
+            0,
+            Raw,
+        ),
+        FunctionInfo::new(
+            PSCI_MIGRATE_INFO_UP_CPU_64,
+            "PSCI_MIGRATE_INFO_UP_CPU_64",
+            PSCI_0_2,
+            0,
+            Raw,
+        ),
+        FunctionInfo::new(PSCI_SYSTEM_OFF, "PSCI_SYSTEM_OFF", PSCI_0_2, 0, NoReturn),
+        FunctionInfo::new(
+            PSCI_SYSTEM_RESET,
+            "PSCI_SYSTEM_RESET",
+            PSCI_0_2,
+            0,
+            NoReturn,
+        ),
+        FunctionInfo::new(PSCI_FEATURES, "PSCI_FEATURES", PSCI_1_0, 1, Value),
+        FunctionInfo::new(PSCI_CPU_FREEZE, "PSCI_CPU_FREEZE", PSCI_1_0, 0, NoReturn),
+        FunctionInfo::new(
+            PSCI_CPU_DEFAULT_SUSPEND_32,
+            "PSCI_CPU_DEFAULT_SUSPEND_32",
+            PSCI_1_0,
+            2,
+            Status,
+        ),
+        FunctionInfo::new(
+            PSCI_CPU_DEFAULT_SUSPEND_64,
+            "PSCI_CPU_DEFAULT_SUSPEND_64",
+            PSCI_1_0,
+            2,
+            Status,
+        ),
+        FunctionInfo::new(
+            PSCI_NODE_HW_STATE_32,
+            "PSCI_NODE_HW_STATE_32",
+            PSCI_1_0,
+            2,
+            Value,
+        ),
+        FunctionInfo::new(
+            PSCI_NODE_HW_STATE_64,
+            "PSCI_NODE_HW_STATE_64",
+            PSCI_1_0,
+            2,
+            Value,
+        ),
+        FunctionInfo::new(
+            PSCI_SYSTEM_SUSPEND_32,
+            "PSCI_SYSTEM_SUSPEND_32",
+            PSCI_1_0,
+            2,
+            Status,
+        ),
+        FunctionInfo::new(
+            PSCI_SYSTEM_SUSPEND_64,
+            "PSCI_SYSTEM_SUSPEND_64",
+            PSCI_1_0,
+            2,
+            Status,
+        ),
+        FunctionInfo::new(
+            PSCI_SET_SUSPEND_MODE,
+            "PSCI_SET_SUSPEND_MODE",
+            PSCI_1_0,
+            1,
+            Status,
+        ),
+        FunctionInfo::new(
+            PSCI_STAT_RESIDENCY_32,
+            "PSCI_STAT_RESIDENCY_32",
+            PSCI_1_0,
+            2,
+            Raw,
+        ),
+        FunctionInfo::new(
+            PSCI_STAT_RESIDENCY_64,
+            "PSCI_STAT_RESIDENCY_64",
+            PSCI_1_0,
+            2,
+            Raw,
+        ),
+        FunctionInfo::new(PSCI_STAT_COUNT_32, "PSCI_STAT_COUNT_32", PSCI_1_0, 2, Raw),
+        FunctionInfo::new(PSCI_STAT_COUNT_64, "PSCI_STAT_COUNT_64", PSCI_1_0, 2, Raw),
+        FunctionInfo::new(
+            PSCI_SYSTEM_RESET2_32,
+            "PSCI_SYSTEM_RESET2_32",
+            PSCI_1_1,
+            2,
+            NoReturn,
+        ),
+        FunctionInfo::new(
+            PSCI_SYSTEM_RESET2_64,
+            "PSCI_SYSTEM_RESET2_64",
+            PSCI_1_1,
+            2,
+            NoReturn,
+        ),
+        FunctionInfo::new(PSCI_MEM_PROTECT, "PSCI_MEM_PROTECT", PSCI_1_1, 1, Value),
+        FunctionInfo::new(
+            PSCI_MEM_PROTECT_CHECK_RANGE_32,
+            "PSCI_MEM_PROTECT_CHECK_RANGE_32",
+            PSCI_1_1,
+            2,
+            Status,
+        ),
+        FunctionInfo::new(
+            PSCI_MEM_PROTECT_CHECK_RANGE_64,
+            "PSCI_MEM_PROTECT_CHECK_RANGE_64",
+            PSCI_1_1,
+            2,
+            Status,
+        ),
+        FunctionInfo::new(
+            PSCI_SYSTEM_OFF2_32,
+            "PSCI_SYSTEM_OFF2_32",
+            PSCI_1_3,
+            2,
+            NoReturn,
+        ),
+        FunctionInfo::new(
+            PSCI_SYSTEM_OFF2_64,
+            "PSCI_SYSTEM_OFF2_64",
+            PSCI_1_3,
+            2,
+            NoReturn,
+        ),
+    ]
+};
+
+/// A call and its arguments, formatted for diagnostics, e.g.
+/// `PSCI_CPU_ON_64(0x1, 0x80000000, 0x0)`.
+///
+/// Known functions are shown by name with the arguments they take. Unknown functions are shown by
+/// function ID, without trailing zero arguments.
+pub(crate) struct FormattedCall<'a> {
+    pub function: FunctionId,
+    pub args: &'a [u64],
+}
+
+impl Display for FormattedCall<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let args = if let Some(info) = lookup(self.function) {
+            write!(f, "{}", info.name)?;
+            &self.args[..info.args.min(self.args.len())]
+        } else {
+            write!(f, "{}", self.function.without_sve_hint())?;
+            let used = self
+                .args
+                .iter()
+                .rposition(|&arg| arg != 0)
+                .map_or(0, |i| i + 1);
+            &self.args[..used]
+        };
+        if self.function.has_sve_hint() {
+            write!(f, "[no SVE]")?;
+        }
+        write!(f, "(")?;
+        for (i, arg) in args.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{arg:#x}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Returns metadata about the given function, ignoring the SVE hint, or `None` if it isn't known.
+pub fn lookup(function: FunctionId) -> Option<&'static FunctionInfo> {
+    let function = function.without_sve_hint();
+    FUNCTIONS.iter().find(|info| info.id == function)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn lookup_known() {
+        let info = lookup(PSCI_CPU_ON_64.with_sve_hint()).unwrap();
+        assert_eq!(info.name, "PSCI_CPU_ON_64");
+        assert_eq!(info.spec, PSCI_0_2);
+        assert_eq!(info.convention(), Convention::Smc64);
+        assert_eq!(info.args, 3);
+        assert_eq!(lookup(PSCI_FEATURES).unwrap().spec, PSCI_1_0);
+        assert_eq!(lookup(PSCI_SYSTEM_OFF2_64).unwrap().spec, PSCI_1_3);
+        assert_eq!(
+            lookup(SMCCC_ARCH_FEATURES).unwrap().spec.to_string(),
+            "SMCCC 1.1"
+        );
+        assert_eq!(lookup(FunctionId::new(0x8400_00ff)), None);
+    }
+
+    #[test]
+    fn consistent() {
+        for (i, info) in FUNCTIONS.iter().enumerate() {
+            assert!(
+                FUNCTIONS[..i].iter().all(|other| other.id != info.id),
+                "{} is duplicated",
+                info.name
+            );
+            assert!(!info.id.has_sve_hint());
+            assert!(info.args <= 3, "{} has too many arguments", info.name);
+            if info.name.ends_with("_64") {
+                assert_eq!(info.convention(), Convention::Smc64, "{}", info.name);
+            } else {
+                assert_eq!(info.convention(), Convention::Smc32, "{}", info.name);
+            }
+        }
+    }
+}
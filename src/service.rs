@@ -0,0 +1,149 @@
+// Copyright 2026 the authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! General service queries, which SMCCC reserves in the function ID range of every owning entity.
+//!
+//! These are always fast SMC32/HVC32 calls, even for services which otherwise use SMC64.
+
+use crate::{
+    Backend, Convention, FunctionId, OwningEntity, Uuid, arch::error::NOT_SUPPORTED,
+    error::positive_or_error_32,
+};
+use core::fmt::{self, Debug, Display, Formatter};
+
+/// The function number of the call count query within each owning entity's range.
+pub const CALL_COUNT: u16 = 0xFF00;
+/// The function number of the UID query within each owning entity's range.
+pub const UID: u16 = 0xFF01;
+/// The function number of the revision query within each owning entity's range.
+pub const REVISION: u16 = 0xFF03;
+
+/// Errors for the general service queries, which are common to every owning entity.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum Error {
+    /// The query is not supported by the service, or there is no such service.
+    #[error("Service query not supported")]
+    NotSupported,
+    /// There was an unexpected return value.
+    #[error("Unknown service query return value {0} ({0:#x})")]
+    Unknown(i32),
+}
+
+impl From<Error> for i32 {
+    fn from(error: Error) -> i32 {
+        match error {
+            Error::NotSupported => NOT_SUPPORTED,
+            Error::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<i32> for Error {
+    fn from(value: i32) -> Self {
+        match value {
+            NOT_SUPPORTED => Error::NotSupported,
+            _ => Error::Unknown(value),
+        }
+    }
+}
+
+/// The revision of a service implementation.
+#[derive(Copy, Clone, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Revision {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl Display for Revision {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl Debug for Revision {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+fn query<C: Backend + ?Sized>(
+    conduit: &C,
+    owning_entity: OwningEntity,
+    function_number: u16,
+) -> Result<[u32; 4], Error> {
+    // An out of range Trusted Application or Trusted OS index can't have a service.
+    let function = FunctionId::fast(Convention::Smc32, owning_entity, function_number)
+        .map_err(|_| Error::NotSupported)?;
+    Ok(conduit.call32_short(function, [0; 3]))
+}
+
+/// Returns the number of calls implemented by the given service.
+///
+/// This call is deprecated by SMCCC, so many implementations don't support it.
+pub fn call_count<C: Backend + ?Sized>(
+    conduit: &C,
+    owning_entity: OwningEntity,
+) -> Result<u32, Error> {
+    positive_or_error_32(query(conduit, owning_entity, CALL_COUNT)?[0])
+}
+
+/// Returns the UID of the implementation of the given service.
+pub fn uid<C: Backend + ?Sized>(conduit: &C, owning_entity: OwningEntity) -> Result<Uuid, Error> {
+    let ret = query(conduit, owning_entity, UID)?;
+    if ret[0] as i32 == NOT_SUPPORTED {
+        Err(Error::NotSupported)
+    } else {
+        Ok(ret.into())
+    }
+}
+
+/// Returns the revision of the implementation of the given service.
+pub fn revision<C: Backend + ?Sized>(
+    conduit: &C,
+    owning_entity: OwningEntity,
+) -> Result<Revision, Error> {
+    let ret = query(conduit, owning_entity, REVISION)?;
+    Ok(Revision {
+        major: positive_or_error_32::<Error>(ret[0])?,
+        minor: ret[1],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::Mock;
+
+    #[test]
+    fn queries() {
+        Mock::expect_call32(FunctionId::new(0x8200_FF00), &[], &[42]);
+        assert_eq!(call_count(&Mock, OwningEntity::Sip), Ok(42));
+        Mock::expect_call32(
+            FunctionId::new(0x8600_FF01),
+            &[],
+            &[0xb66fb428, 0xe911c52e, 0x564bcaa9, 0x743a004d],
+        );
+        assert_eq!(
+            uid(&Mock, OwningEntity::VendorHypervisor),
+            Ok(Uuid::from_u128(0x28b46fb6_2ec5_11e9_a9ca_4b564d003a74))
+        );
+        Mock::expect_call32(FunctionId::new(0xBF00_FF03), &[], &[1, 2]);
+        assert_eq!(
+            revision(&Mock, OwningEntity::TrustedOs(13)),
+            Ok(Revision { major: 1, minor: 2 })
+        );
+        Mock::expect_call32(FunctionId::new(0x8300_FF01), &[], &[u32::MAX]);
+        assert_eq!(uid(&Mock, OwningEntity::Oem), Err(Error::NotSupported));
+        Mock::expect_call32(FunctionId::new(0x8400_FF03), &[], &[-2i32 as u32]);
+        assert_eq!(
+            revision(&Mock, OwningEntity::StandardSecure),
+            Err(Error::Unknown(-2))
+        );
+        Mock::finish();
+        assert_eq!(
+            call_count(&Mock, OwningEntity::TrustedOs(14)),
+            Err(Error::NotSupported)
+        );
+    }
+}
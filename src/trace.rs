@@ -0,0 +1,582 @@
+// Copyright 2026 the authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Recording SMCCC calls to a text trace, and replaying them on any host.
+//!
+//! A [`Recorder`] wraps a real conduit and writes each call it makes and the values returned. Each
+//! call is written before it is made, so calls which don't return such as `CPU_OFF` are still
+//! recorded. The trace can later be loaded into a [`Replay`], which returns the recorded results to
+//! the same sequence of calls and reports the first call which diverges from the trace. This allows
+//! a firmware bug seen on real hardware to become a regression test.
+//!
+//! # Format
+//!
+//! Each call is one line, consisting of the calling convention (`32` or `64`), the function ID,
+//! the argument registers, `->`, and the result registers, separated by spaces. All values are in
+//! hexadecimal without a prefix. Trailing zero arguments and results are omitted, but at least one
+//! result is always given. A call which didn't return has no `->` or results, and replaying it
+//! returns zero in every result register. Blank lines and lines starting with `#` are ignored. For
+//! example:
+//!
+//! ```text
+//! # PSCI_VERSION returns 1.1.
+//! 32 84000000 -> 10001
+//! # PSCI_CPU_ON_64 for MPIDR 1, which is already on.
+//! 64 c4000003 1 80000000 2a -> fffffffffffffffc
+//! # PSCI_CPU_OFF, which doesn't return.
+//! 32 84000002
+//! ```
+
+use crate::{Backend, Backend64, Convention, FunctionId, arch::error::NOT_SUPPORTED};
+use core::{
+    cell::{Cell, OnceCell, RefCell},
+    fmt::{self, Display, Formatter, Write},
+    str::{FromStr, Lines},
+};
+
+/// A call made via a conduit, without its results.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct TracedCall {
+    /// Whether the call was made with the 32-bit or 64-bit calling convention.
+    pub convention: Convention,
+    /// The function ID passed in w0.
+    pub function: FunctionId,
+    /// The argument registers x1 to x17. For 32-bit calls only the first 7 are used.
+    pub args: [u64; 17],
+}
+
+impl Display for TracedCall {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let width = match self.convention {
+            Convention::Smc32 => 32,
+            Convention::Smc64 => 64,
+        };
+        write!(f, "{width} {:x}", u32::from(self.function))?;
+        for arg in trim_zeros(&self.args, 0) {
+            write!(f, " {arg:x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A call made via a conduit, and the values it returned.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Entry {
+    /// The call which was made.
+    pub call: TracedCall,
+    /// The result registers x0 to x17, or `None` if the call didn't return. For 32-bit calls only
+    /// the first 8 are used.
+    pub results: Option<[u64; 18]>,
+}
+
+impl Display for Entry {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.call)?;
+        if let Some(results) = &self.results {
+            write_results(f, results)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the `->` separator and the given result registers.
+fn write_results(writer: &mut impl Write, results: &[u64]) -> fmt::Result {
+    write!(writer, " ->")?;
+    for result in trim_zeros(results, 1) {
+        write!(writer, " {result:x}")?;
+    }
+    Ok(())
+}
+
+impl FromStr for Entry {
+    type Err = ParseEntryError;
+
+    fn from_str(s: &str) -> Result<Self, ParseEntryError> {
+        let mut tokens = s.split_whitespace();
+        let (convention, max_args, max_value) = match tokens.next() {
+            Some("32") => (Convention::Smc32, 7, u32::MAX.into()),
+            Some("64") => (Convention::Smc64, 17, u64::MAX),
+            _ => return Err(ParseEntryError),
+        };
+        let function = tokens
+            .next()
+            .and_then(|token| u32::from_str_radix(token, 16).ok())
+            .and_then(|raw| FunctionId::from_raw(raw).ok())
+            .ok_or(ParseEntryError)?;
+        let mut args = [0; 17];
+        let (_, returned) = parse_values(&mut tokens, &mut args[..max_args], max_value)?;
+        let results = if returned {
+            let mut results = [0; 18];
+            let (count, separator) =
+                parse_values(&mut tokens, &mut results[..=max_args], max_value)?;
+            if count == 0 || separator {
+                return Err(ParseEntryError);
+            }
+            Some(results)
+        } else {
+            None
+        };
+        Ok(Self {
+            call: TracedCall {
+                convention,
+                function,
+                args,
+            },
+            results,
+        })
+    }
+}
+
+/// Error parsing an [`Entry`] from a line of a trace.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, thiserror::Error)]
+#[error("Invalid trace entry")]
+pub struct ParseEntryError;
+
+/// Parses hexadecimal values from the given tokens into `values` until a `->` separator or the end
+/// of the tokens, and returns how many there were and whether the separator was found.
+fn parse_values<'a>(
+    tokens: &mut impl Iterator<Item = &'a str>,
+    values: &mut [u64],
+    max_value: u64,
+) -> Result<(usize, bool), ParseEntryError> {
+    let mut count = 0;
+    loop {
+        let token = match tokens.next() {
+            None => return Ok((count, false)),
+            Some("->") => return Ok((count, true)),
+            Some(token) => token,
+        };
+        let value = u64::from_str_radix(token, 16)
+            .ok()
+            .filter(|&value| value <= max_value)
+            .ok_or(ParseEntryError)?;
+        *values.get_mut(count).ok_or(ParseEntryError)? = value;
+        count += 1;
+    }
+}
+
+/// Returns the given values without trailing zeros, but keeping at least `min_len` values.
+fn trim_zeros(values: &[u64], min_len: usize) -> &[u64] {
+    let len = values
+        .iter()
+        .rposition(|&value| value != 0)
+        .map_or(0, |i| i + 1);
+    &values[..len.max(min_len)]
+}
+
+/// Wraps another [`Backend`] to write every call made through it to a trace.
+pub struct Recorder<B, W> {
+    inner: B,
+    writer: RefCell<W>,
+    result: Cell<fmt::Result>,
+}
+
+impl<B, W: Write> Recorder<B, W> {
+    /// Creates a new wrapper around the given backend, which writes calls to the given writer.
+    pub const fn new(inner: B, writer: W) -> Self {
+        Self {
+            inner,
+            writer: RefCell::new(writer),
+            result: Cell::new(Ok(())),
+        }
+    }
+
+    /// Writes a comment line to the trace, e.g. to describe the platform it was recorded on.
+    pub fn comment(&self, comment: &str) {
+        self.write(|writer| writeln!(writer, "# {comment}"));
+    }
+
+    /// Returns the writer, or an error if writing any entry to it failed.
+    pub fn finish(self) -> Result<W, fmt::Error> {
+        self.result.get()?;
+        Ok(self.writer.into_inner())
+    }
+
+    /// Writes a call which is about to be made, without ending the line so that its results can
+    /// follow.
+    fn record_call<const N: usize>(
+        &self,
+        convention: Convention,
+        function: FunctionId,
+        args: [u64; N],
+    ) {
+        let mut call = TracedCall {
+            convention,
+            function,
+            args: [0; 17],
+        };
+        call.args[..N].copy_from_slice(&args);
+        self.write(|writer| write!(writer, "{call}"));
+    }
+
+    /// Writes the results of the call most recently recorded, and ends its line.
+    fn record_results<const M: usize>(&self, results: [u64; M]) {
+        self.write(|writer| {
+            write_results(writer, &results)?;
+            writeln!(writer)
+        });
+    }
+
+    fn write(&self, write: impl FnOnce(&mut W) -> fmt::Result) {
+        if self.result.get().is_ok() {
+            self.result.set(write(&mut self.writer.borrow_mut()));
+        }
+    }
+}
+
+impl<B: Backend, W: Write> Backend for Recorder<B, W> {
+    fn call32(&self, function: FunctionId, args: [u32; 7]) -> [u32; 8] {
+        self.record_call(Convention::Smc32, function, args.map(u64::from));
+        let ret = self.inner.call32(function, args);
+        self.record_results(ret.map(u64::from));
+        ret
+    }
+
+    fn call32_short(&self, function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        self.record_call(Convention::Smc32, function, args.map(u64::from));
+        let ret = self.inner.call32_short(function, args);
+        self.record_results(ret.map(u64::from));
+        ret
+    }
+
+    fn call32_short_v1_1(&self, function: FunctionId, args: [u32; 3]) -> [u32; 4] {
+        self.record_call(Convention::Smc32, function, args.map(u64::from));
+        let ret = self.inner.call32_short_v1_1(function, args);
+        self.record_results(ret.map(u64::from));
+        ret
+    }
+}
+
+impl<B: Backend64, W: Write> Backend64 for Recorder<B, W> {
+    fn call64(&self, function: FunctionId, args: [u64; 17]) -> [u64; 18] {
+        self.record_call(Convention::Smc64, function, args);
+        let ret = self.inner.call64(function, args);
+        self.record_results(ret);
+        ret
+    }
+
+    fn call64_short(&self, function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        self.record_call(Convention::Smc64, function, args);
+        let ret = self.inner.call64_short(function, args);
+        self.record_results(ret);
+        ret
+    }
+
+    fn call64_short_v1_1(&self, function: FunctionId, args: [u64; 3]) -> [u64; 4] {
+        self.record_call(Convention::Smc64, function, args);
+        let ret = self.inner.call64_short_v1_1(function, args);
+        self.record_results(ret);
+        ret
+    }
+}
+
+/// The first way in which a replayed sequence of calls diverged from the trace.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ReplayError {
+    /// A line of the trace couldn't be parsed.
+    #[error("Line {line}: invalid trace entry")]
+    InvalidEntry {
+        /// The 1-based line number in the trace.
+        line: usize,
+    },
+    /// A call was made with a different function ID, calling convention or arguments than the
+    /// trace recorded.
+    #[error("Line {line}: expected call {expected} but got {actual}")]
+    Mismatch {
+        /// The 1-based line number in the trace.
+        line: usize,
+        /// The entry recorded in the trace.
+        expected: Entry,
+        /// The call which was made.
+        actual: TracedCall,
+    },
+    /// A call was made after the end of the trace.
+    #[error("Unexpected call {actual} after end of trace")]
+    UnexpectedCall {
+        /// The call which was made.
+        actual: TracedCall,
+    },
+    /// The trace had calls left over which weren't made.
+    #[error("Line {line}: expected call {expected} but no more calls were made")]
+    Unconsumed {
+        /// The 1-based line number in the trace.
+        line: usize,
+        /// The first entry which wasn't replayed.
+        expected: Entry,
+    },
+}
+
+/// A [`Backend`] which replays the results of calls recorded in a trace.
+///
+/// Once a call diverges from the trace, it and every later call return `NOT_SUPPORTED`, and the
+/// divergence is reported by [`Replay::finish`].
+pub struct Replay<'a> {
+    state: RefCell<ReplayState<'a>>,
+    /// The first divergence from the trace, once there is one.
+    error: OnceCell<ReplayError>,
+}
+
+struct ReplayState<'a> {
+    lines: Lines<'a>,
+    /// The number of lines consumed so far.
+    line: usize,
+}
+
+impl ReplayState<'_> {
+    /// Returns the next entry in the trace and its line number, skipping comments and blank lines,
+    /// or the line number of an invalid entry.
+    fn next_entry(&mut self) -> Result<Option<(usize, Entry)>, usize> {
+        for line in self.lines.by_ref() {
+            self.line += 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            return match line.parse() {
+                Ok(entry) => Ok(Some((self.line, entry))),
+                Err(ParseEntryError) => Err(self.line),
+            };
+        }
+        Ok(None)
+    }
+}
+
+impl<'a> Replay<'a> {
+    /// Creates a new replay of the given trace.
+    pub fn new(trace: &'a str) -> Self {
+        Self {
+            state: RefCell::new(ReplayState {
+                lines: trace.lines(),
+                line: 0,
+            }),
+            error: OnceCell::new(),
+        }
+    }
+
+    /// Checks that all calls in the trace were made and none diverged, or else returns the first
+    /// divergence.
+    pub fn finish(&self) -> Result<(), &ReplayError> {
+        if self.error.get().is_none() {
+            let error = match self.state.borrow_mut().next_entry() {
+                Ok(None) => return Ok(()),
+                Ok(Some((line, expected))) => ReplayError::Unconsumed { line, expected },
+                Err(line) => ReplayError::InvalidEntry { line },
+            };
+            self.error.get_or_init(|| error);
+        }
+        self.error.get().map_or(Ok(()), Err)
+    }
+
+    fn replay(&self, actual: TracedCall) -> [u64; 18] {
+        if self.error.get().is_none() {
+            let error = match self.state.borrow_mut().next_entry() {
+                Ok(Some((_, expected))) if expected.call == actual => {
+                    return expected.results.unwrap_or_default();
+                }
+                Ok(Some((line, expected))) => ReplayError::Mismatch {
+                    line,
+                    expected,
+                    actual,
+                },
+                Ok(None) => ReplayError::UnexpectedCall { actual },
+                Err(line) => ReplayError::InvalidEntry { line },
+            };
+            self.error.get_or_init(|| error);
+        }
+        let mut results = [0; 18];
+        results[0] = NOT_SUPPORTED as u64;
+        results
+    }
+}
+
+impl Backend for Replay<'_> {
+    fn call32(&self, function: FunctionId, args: [u32; 7]) -> [u32; 8] {
+        let mut full_args = [0; 17];
+        for (full_arg, arg) in full_args.iter_mut().zip(args) {
+            *full_arg = arg.into();
+        }
+        let results = self.replay(TracedCall {
+            convention: Convention::Smc32,
+            function,
+            args: full_args,
+        });
+        core::array::from_fn(|i| results[i] as u32)
+    }
+}
+
+impl Backend64 for Replay<'_> {
+    fn call64(&self, function: FunctionId, args: [u64; 17]) -> [u64; 18] {
+        self.replay(TracedCall {
+            convention: Convention::Smc64,
+            function,
+            args,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::Mock,
+        psci::{self, Error, Version},
+    };
+    use std::string::{String, ToString};
+
+    const TRACE: &str = "\
+# Bring up a secondary core.
+32 84000000 -> 10001
+64 c4000003 1 80000000 2a -> fffffffffffffffc
+
+32 84000002 -> fffffffd
+";
+
+    #[test]
+    fn record() {
+        let recorder = Recorder::new(Mock, String::new());
+        recorder.comment("Bring up a secondary core.");
+        Mock::expect_call32(psci::PSCI_VERSION, &[], &[0x0001_0001]);
+        Mock::expect_call64(psci::PSCI_CPU_ON_64, &[1, 0x8000_0000, 42], &[-4i64 as u64]);
+        assert_eq!(
+            psci::backend::version(&recorder),
+            Ok(Version { major: 1, minor: 1 })
+        );
+        assert_eq!(
+            psci::backend::cpu_on(&recorder, 1, 0x8000_0000, 42),
+            Err(Error::AlreadyOn)
+        );
+        Mock::finish();
+        assert_eq!(
+            recorder.finish().unwrap(),
+            "# Bring up a secondary core.\n\
+             32 84000000 -> 10001\n\
+             64 c4000003 1 80000000 2a -> fffffffffffffffc\n"
+        );
+    }
+
+    /// A writer whose contents can be inspected while the recorder is still using it.
+    struct SharedWriter<'a>(&'a RefCell<String>);
+
+    impl Write for SharedWriter<'_> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0.borrow_mut().push_str(s);
+            Ok(())
+        }
+    }
+
+    /// A backend which checks that the call has already been written to the trace.
+    struct ExpectRecorded<'a>(&'a RefCell<String>);
+
+    impl Backend for ExpectRecorded<'_> {
+        fn call32(&self, function: FunctionId, _args: [u32; 7]) -> [u32; 8] {
+            assert_eq!(function, psci::PSCI_SYSTEM_OFF);
+            assert_eq!(*self.0.borrow(), "32 84000008");
+            [0; 8]
+        }
+    }
+
+    #[test]
+    fn record_before_call() {
+        let trace = RefCell::new(String::new());
+        let recorder = Recorder::new(ExpectRecorded(&trace), SharedWriter(&trace));
+        assert_eq!(psci::backend::system_off(&recorder), Ok(()));
+        assert!(recorder.finish().is_ok());
+        assert_eq!(*trace.borrow(), "32 84000008 -> 0\n");
+    }
+
+    #[test]
+    fn replay_call_without_return() {
+        let entry = "32 84000008".parse::<Entry>().unwrap();
+        assert_eq!(entry.results, None);
+        assert_eq!(entry.to_string(), "32 84000008");
+
+        let replay = Replay::new("32 84000000 -> 10001\n32 84000008");
+        assert_eq!(
+            psci::backend::version(&replay),
+            Ok(Version { major: 1, minor: 1 })
+        );
+        assert_eq!(psci::backend::system_off(&replay), Ok(()));
+        assert_eq!(replay.finish(), Ok(()));
+    }
+
+    #[test]
+    fn replay() {
+        let replay = Replay::new(TRACE);
+        assert_eq!(
+            psci::backend::version(&replay),
+            Ok(Version { major: 1, minor: 1 })
+        );
+        assert_eq!(
+            psci::backend::cpu_on(&replay, 1, 0x8000_0000, 42),
+            Err(Error::AlreadyOn)
+        );
+        assert_eq!(psci::backend::cpu_off(&replay), Err(Error::Denied));
+        assert_eq!(replay.finish(), Ok(()));
+    }
+
+    #[test]
+    fn replay_mismatch() {
+        let replay = Replay::new(TRACE);
+        assert_eq!(
+            psci::backend::version(&replay),
+            Ok(Version { major: 1, minor: 1 })
+        );
+        assert_eq!(
+            psci::backend::cpu_on(&replay, 2, 0x8000_0000, 42),
+            Err(Error::NotSupported)
+        );
+        // Later calls fail too, and the first mismatch is kept.
+        assert_eq!(psci::backend::cpu_off(&replay), Err(Error::NotSupported));
+        let error = replay.finish().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Line 3: expected call 64 c4000003 1 80000000 2a -> fffffffffffffffc but got \
+             64 c4000003 2 80000000 2a"
+        );
+    }
+
+    #[test]
+    fn replay_wrong_length() {
+        let replay = Replay::new(TRACE);
+        assert_eq!(
+            psci::backend::version(&replay),
+            Ok(Version { major: 1, minor: 1 })
+        );
+        assert!(matches!(
+            replay.finish(),
+            Err(ReplayError::Unconsumed { line: 3, .. })
+        ));
+
+        let replay = Replay::new("32 84000000 -> 10001");
+        assert_eq!(
+            psci::backend::version(&replay),
+            Ok(Version { major: 1, minor: 1 })
+        );
+        assert_eq!(psci::backend::cpu_off(&replay), Err(Error::NotSupported));
+        assert!(matches!(
+            replay.finish(),
+            Err(ReplayError::UnexpectedCall { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_invalid_entries() {
+        for line in [
+            "",
+            "16 84000000 -> 0",
+            "32 84000000 -> 0 -> 0",
+            "32 84000000 ->",
+            "32 84800000 -> 0",
+            "32 84000000 100000000 -> 0",
+            "32 84000000 1 2 3 4 5 6 7 8 -> 0",
+            "32 84000000 -> 0 1 2 3 4 5 6 7 8",
+            "64 c4000003 x -> 0",
+        ] {
+            assert_eq!(line.parse::<Entry>(), Err(ParseEntryError), "{line}");
+        }
+        let replay = Replay::new("# Comment\n32 84000000 -> 10001 ->");
+        assert_eq!(psci::backend::version(&replay), Err(Error::NotSupported));
+        assert_eq!(replay.finish(), Err(&ReplayError::InvalidEntry { line: 2 }));
+    }
+}
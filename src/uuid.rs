@@ -0,0 +1,163 @@
+// Copyright 2026 the authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! A UUID type, with conversions to and from the register layout used by SMCCC calls.
+
+use core::{
+    fmt::{self, Debug, Display, Formatter},
+    str::FromStr,
+};
+
+/// The positions of the hyphens in the canonical string form of a UUID.
+const HYPHENS: [usize; 4] = [8, 13, 18, 23];
+
+/// Error parsing a UUID from a string.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, thiserror::Error)]
+#[error("Invalid UUID string")]
+pub struct ParseUuidError;
+
+/// A 128-bit universally unique identifier, such as returned by the UID query of an SMCCC service.
+///
+/// SMCCC calls return a UUID in w0 to w3, with bytes 0 to 3 of the UUID in w0, bytes 4 to 7 in w1
+/// and so on, each register holding its four bytes in little-endian order.
+#[derive(Copy, Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Uuid([u8; 16]);
+
+impl Uuid {
+    /// Returns the UUID with the given bytes, in the order they appear in the string form.
+    pub const fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns the UUID whose string form is the given value in hexadecimal.
+    ///
+    /// For example, `Uuid::from_u128(0x28b46fb6_2ec5_11e9_a9ca_4b564d003a74)`.
+    pub const fn from_u128(value: u128) -> Self {
+        Self(value.to_be_bytes())
+    }
+
+    /// Returns the UUID packed in the given w0 to w3 register values.
+    pub const fn from_registers(registers: [u32; 4]) -> Self {
+        let mut bytes = [0; 16];
+        let mut i = 0;
+        while i < 4 {
+            let word = registers[i].to_le_bytes();
+            bytes[i * 4] = word[0];
+            bytes[i * 4 + 1] = word[1];
+            bytes[i * 4 + 2] = word[2];
+            bytes[i * 4 + 3] = word[3];
+            i += 1;
+        }
+        Self(bytes)
+    }
+
+    /// Returns the bytes of the UUID, in the order they appear in the string form.
+    pub const fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+
+    /// Returns the w0 to w3 register values used to pass this UUID.
+    pub const fn to_registers(self) -> [u32; 4] {
+        let b = self.0;
+        [
+            u32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+            u32::from_le_bytes([b[4], b[5], b[6], b[7]]),
+            u32::from_le_bytes([b[8], b[9], b[10], b[11]]),
+            u32::from_le_bytes([b[12], b[13], b[14], b[15]]),
+        ]
+    }
+}
+
+impl From<[u32; 4]> for Uuid {
+    fn from(registers: [u32; 4]) -> Self {
+        Self::from_registers(registers)
+    }
+}
+
+impl From<Uuid> for [u32; 4] {
+    fn from(uuid: Uuid) -> Self {
+        uuid.to_registers()
+    }
+}
+
+impl FromStr for Uuid {
+    type Err = ParseUuidError;
+
+    /// Parses a UUID from the canonical form, e.g. `28b46fb6-2ec5-11e9-a9ca-4b564d003a74`.
+    fn from_str(s: &str) -> Result<Self, ParseUuidError> {
+        let s = s.as_bytes();
+        if s.len() != 36 || HYPHENS.iter().any(|&i| s[i] != b'-') {
+            return Err(ParseUuidError);
+        }
+        let mut digits = s
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !HYPHENS.contains(i))
+            .map(|(_, &c)| char::from(c).to_digit(16).ok_or(ParseUuidError));
+        let mut bytes = [0; 16];
+        for byte in &mut bytes {
+            // There are exactly 32 digits left once the hyphens are removed.
+            let high = digits.next().unwrap()?;
+            let low = digits.next().unwrap()?;
+            *byte = (high << 4 | low) as u8;
+        }
+        Ok(Self(bytes))
+    }
+}
+
+impl Display for Uuid {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for (i, byte) in self.0.iter().enumerate() {
+            if matches!(i, 4 | 6 | 8 | 10) {
+                write!(f, "-")?;
+            }
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for Uuid {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+
+    /// The UID of the KVM vendor hypervisor service, and the registers it is returned in.
+    const KVM_UUID: Uuid = Uuid::from_u128(0x28b46fb6_2ec5_11e9_a9ca_4b564d003a74);
+    const KVM_REGISTERS: [u32; 4] = [0xb66fb428, 0xe911c52e, 0x564bcaa9, 0x743a004d];
+
+    #[test]
+    fn registers_round_trip() {
+        assert_eq!(KVM_UUID.to_registers(), KVM_REGISTERS);
+        assert_eq!(Uuid::from(KVM_REGISTERS), KVM_UUID);
+    }
+
+    #[test]
+    fn string_round_trip() {
+        let string = "28b46fb6-2ec5-11e9-a9ca-4b564d003a74";
+        assert_eq!(string.parse(), Ok(KVM_UUID));
+        assert_eq!("28B46FB6-2EC5-11E9-A9CA-4B564D003A74".parse(), Ok(KVM_UUID));
+        assert_eq!(KVM_UUID.to_string(), string);
+    }
+
+    #[test]
+    fn invalid_strings() {
+        for string in [
+            "",
+            "28b46fb6-2ec5-11e9-a9ca-4b564d003a7",
+            "28b46fb6-2ec5-11e9-a9ca-4b564d003a745",
+            "28b46fb62ec5-11e9-a9ca-4b564d003a74-",
+            "28b46fb6-2ec5-11e9-a9ca-4b564d003a7g",
+            "+8b46fb6-2ec5-11e9-a9ca-4b564d003a74",
+        ] {
+            assert_eq!(string.parse::<Uuid>(), Err(ParseUuidError), "{string}");
+        }
+    }
+}